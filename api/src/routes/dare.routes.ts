@@ -18,12 +18,21 @@ const router = Router();
 
 // ── POST /api/dares ───────────────────────────────────────────────────────
 // Create dare metadata (after on-chain tx confirms).
+
+// Must match MAX_DESCRIPTION_LEN in the program: reveal_description takes the text
+// as an instruction argument, so longer descriptions could never be revealed on-chain.
+const MAX_DESCRIPTION_BYTES = 800;
+
 const createDareSchema = z.object({
     onChainId: z.coerce.number().int().positive(),
     darePDA: z.string().min(32).max(64),
     vaultPDA: z.string().min(32).max(64),
     title: z.string().min(1).max(200),
-    description: z.string().min(1).max(5000),
+    // Trimmed here so the stored text and descriptionHash are exactly what clients hash on-chain
+    description: z.string().trim().min(1).refine(
+        (description) => Buffer.byteLength(description, "utf8") <= MAX_DESCRIPTION_BYTES,
+        { message: `Description must be at most ${MAX_DESCRIPTION_BYTES} bytes` },
+    ),
     amount: z.coerce.number().int().positive(), // lamports
     dareType: z.enum(["DIRECT_DARE", "PUBLIC_BOUNTY"]),
     winnerSelection: z.enum(["CHALLENGER_SELECT", "COMMUNITY_VOTE"]),
@@ -30,16 +30,47 @@ function anchorDiscriminator(name: string): string {
     return hash.slice(0, 16); // 8 bytes = 16 hex chars
 }
 
-const DISCRIMINATORS: Record<string, string> = {
-    create_dare: anchorDiscriminator("create_dare"),
-    accept_dare: anchorDiscriminator("accept_dare"),
-    refuse_dare: anchorDiscriminator("refuse_dare"),
-    submit_proof: anchorDiscriminator("submit_proof"),
-    approve_dare: anchorDiscriminator("approve_dare"),
-    reject_dare: anchorDiscriminator("reject_dare"),
-    cancel_dare: anchorDiscriminator("cancel_dare"),
-    expire_dare: anchorDiscriminator("expire_dare"),
-};
+// Every instruction in the program (web/lib/idl/contract.json), so the indexer recognizes all of
+// them even where the DB has nothing to update.
+const INSTRUCTION_NAMES = [
+    "accept_dare",
+    "accept_deadline_extension",
+    "allow_mint",
+    "approve_dare",
+    "back_dare",
+    "cancel_dare",
+    "cast_vote",
+    "claim_refund",
+    "close_dare",
+    "create_dare",
+    "disallow_mint",
+    "dispute_rejection",
+    "expire_dare",
+    "increase_stake",
+    "initialize_config",
+    "migrate_dare",
+    "migrate_user_stats",
+    "pause",
+    "propose_deadline_extension",
+    "refuse_dare",
+    "reject_dare",
+    "resolve_dispute",
+    "reveal_description",
+    "settle_with_attestation",
+    "submit_proof",
+    "tally_votes",
+    "unpause",
+    "update_arbiter",
+    "update_dispute_window_bounds",
+    "update_fee",
+    "update_guardian",
+    "update_oracle",
+    "update_treasury",
+];
+
+const DISCRIMINATORS: Record<string, string> = Object.fromEntries(
+    INSTRUCTION_NAMES.map((name) => [name, anchorDiscriminator(name)])
+);
 
 // Build reverse lookup: hex discriminator → instruction name
 const DISC_TO_NAME: Record<string, string> = {};
@@ -94,7 +125,7 @@ async function processTransaction(tx: any) {
 
         if (instructionType) {
             console.log(`  ✅ Matched instruction: ${instructionType}`);
-            await handleInstruction(instructionType, accounts, tx);
+            await handleInstruction(instructionType, accounts, data, tx);
         } else {
             // Also check inner instructions
             const innerIxs = ix.innerInstructions || [];
@@ -102,7 +133,7 @@ async function processTransaction(tx: any) {
                 const innerType = parseInstructionFromData(inner.data);
                 if (innerType) {
                     console.log(`  ✅ Matched inner instruction: ${innerType}`);
-                    await handleInstruction(innerType, inner.accounts || accounts, tx);
+                    await handleInstruction(innerType, inner.accounts || accounts, inner.data, tx);
                 }
             }
         }
@@ -154,7 +185,7 @@ function base58ToHex(input: string): string {
 
 // ── Instruction Router ───────────────────────────────────────────────────
 
-async function handleInstruction(instructionType: string, accounts: string[], tx: any) {
+async function handleInstruction(instructionType: string, accounts: string[], data: string, tx: any) {
     switch (instructionType) {
         case "create_dare":
             await handleCreateDare(accounts, tx);
@@ -180,8 +211,16 @@ async function handleInstruction(instructionType: string, accounts: string[], tx
         case "expire_dare":
             await handleExpireDare(accounts, tx);
             break;
+        case "settle_with_attestation":
+            // args: approve(bool), right after the 8-byte discriminator
+            if (base58ToHex(data).slice(16, 18) === "01") {
+                await handleApproveDare(accounts, tx);
+            } else {
+                await handleRejectDare(accounts, tx);
+            }
+            break;
         default:
-            console.log(`  ⚠️ Unknown instruction type: ${instructionType}`);
+            console.log(`  ℹ️ No DB change for instruction: ${instructionType}`);
     }
 }
 
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::ed25519_program;
+
+use crate::error::DareMeError;
+
+/// Bytes the oracle signs to settle a proof: dare key, the dare's created_at (i64 LE), proof hash,
+/// the proof attempt index (u32 LE), winners paid so far, then 1 to approve or 0 to reject.
+/// The attempt and payout count pin a signature to one review, so it can't be replayed against a
+/// resubmission of the same proof, a later milestone, or a dare recreated at the same address.
+pub fn attestation_message(
+    dare: &Pubkey,
+    created_at: i64,
+    proof_hash: &[u8; 32],
+    attempt: u32,
+    winners_paid: u8,
+    approve: bool,
+) -> [u8; 78] {
+    let mut message = [0u8; 78];
+    message[..32].copy_from_slice(dare.as_ref());
+    message[32..40].copy_from_slice(&created_at.to_le_bytes());
+    message[40..72].copy_from_slice(proof_hash);
+    message[72..76].copy_from_slice(&attempt.to_le_bytes());
+    message[76] = winners_paid;
+    message[77] = approve as u8;
+    message
+}
+
+/// Checks that the instruction right before the current one is an Ed25519 program instruction
+/// verifying a single signature by `oracle` over `message`.
+/// The runtime fails the whole transaction if that signature doesn't verify, so finding the
+/// instruction with the expected key and message is all the program has to do.
+pub fn verify_attestation(instructions_sysvar: &AccountInfo, oracle: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, DareMeError::MissingAttestation);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, DareMeError::MissingAttestation);
+
+    // Header: signature count and padding, then one set of 7 little-endian u16 offsets
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, DareMeError::InvalidAttestation);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_ix = read_u16(4);
+    let pubkey_offset = read_u16(6) as usize;
+    let pubkey_ix = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix = read_u16(14);
+
+    // Key, signature and message must all live in the Ed25519 instruction itself,
+    // not be borrowed from another instruction in the transaction
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        DareMeError::InvalidAttestation
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + 32)
+        .ok_or(DareMeError::InvalidAttestation)?;
+    require!(pubkey == oracle.as_ref(), DareMeError::InvalidAttestation);
+
+    let signed = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(DareMeError::InvalidAttestation)?;
+    require!(signed == message, DareMeError::InvalidAttestation);
+
+    Ok(())
+}
@@ -1,11 +1,102 @@
 pub const DARE_SEED: &[u8] = b"dare";
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const USER_STATS_SEED: &[u8] = b"user_stats";
+pub const VOTE_SEED: &[u8] = b"vote";
+pub const SUBMISSION_SEED: &[u8] = b"submission";
+pub const BACKER_SEED: &[u8] = b"backer";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const PROOF_ATTEMPT_SEED: &[u8] = b"proof_attempt";
+pub const DEALINGS_SEED: &[u8] = b"dealings";
+pub const ALLOWED_MINT_SEED: &[u8] = b"allowed_mint";
+
+/// Current account layouts. Accounts with an older `version` must go through
+/// migrate_dare / migrate_user_stats before any other instruction accepts them.
+/// Version 0 is the original fixed layout, which had no version byte.
+pub const DARE_VERSION: u8 = 1;
+pub const USER_STATS_VERSION: u8 = 1;
+
+/// Sizes of the version 0 layouts, which is how migrations recognise them
+pub const LEGACY_DARE_SPACE: usize = 191;
+pub const LEGACY_USER_STATS_SPACE: usize = 73;
+
+/// High bit marking dare IDs assigned by the program from the challenger's counter.
+/// Client-picked IDs (e.g. millisecond timestamps) stay below it, so the two never collide.
+pub const ASSIGNED_DARE_ID_FLAG: u64 = 1 << 63;
 
 /// Maximum deadline duration: 30 days in seconds
 pub const MAX_DEADLINE_DURATION: i64 = 30 * 24 * 60 * 60;
 
-/// Dispute window: 72 hours
-/// After proof is submitted, challenger has 72h to approve/reject.
+/// Default dispute window: 72 hours, used when a dare doesn't pick its own.
+/// After proof is submitted, the challenger has the dare's window to approve/reject.
 /// If no action, anyone can call expire_dare to auto-release funds to daree.
 pub const DISPUTE_WINDOW: i64 = 72 * 60 * 60;
+
+/// Hard bounds on the admin-set range for per-dare dispute windows: 1 hour to 14 days
+pub const MIN_DISPUTE_WINDOW: i64 = 60 * 60;
+pub const MAX_DISPUTE_WINDOW: i64 = 14 * 24 * 60 * 60;
+
+/// Range a fresh config allows for per-dare dispute windows: 24 hours to 7 days
+pub const DEFAULT_MIN_DISPUTE_WINDOW: i64 = 24 * 60 * 60;
+pub const DEFAULT_MAX_DISPUTE_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+/// How long the arbiter has to rule on a dispute: 14 days.
+/// After that expire_dare lapses the dispute, returning the daree's stake and refunding the reward.
+pub const ARBITER_TIMEOUT: i64 = 14 * 24 * 60 * 60;
+
+/// Voting period for CommunityVote dares: 48 hours
+/// Opens when proof is submitted; tally_votes can settle once it closes.
+pub const VOTING_PERIOD: i64 = 48 * 60 * 60;
+
+/// Minimum number of votes for the community to reject a proof.
+/// Below quorum the proof stands and the daree is paid.
+pub const VOTE_QUORUM: u32 = 3;
+
+/// Maximum number of payout slots: paid winners on a split-payout PublicBounty,
+/// or milestones on a DirectDare
+pub const MAX_WINNERS: usize = 5;
+
+/// Maximum number of check-in periods on a streak dare (one bit each in `Dare.checkins`)
+pub const MAX_STREAK_PERIODS: u8 = 64;
+
+/// Longest description reveal_description accepts, in UTF-8 bytes. The text is an instruction argument,
+/// so it has to fit in one ~1232-byte transaction next to the signatures, accounts and a priority fee.
+/// The API caps descriptions to the same length so every committed hash can be revealed.
+pub const MAX_DESCRIPTION_LEN: usize = 800;
+
+/// Basis-point denominator for payout shares (10_000 = 100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Hard cap on the protocol fee: 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Hard cap on a dare's judge fee: 10%
+pub const MAX_JUDGE_FEE_BPS: u16 = 1_000;
+
+/// Reputation a daree earns for completing a dare, on top of the amount bonus
+pub const REPUTATION_COMPLETION_POINTS: i64 = 10;
+
+/// Payouts are counted for reputation in hundredths of a whole SOL or token, read through the mint's
+/// decimals, so a unit is 0.01 SOL on SOL dares and 0.01 of the token on token dares.
+/// Each payout earns the square root of its units, so bigger dares count for more without a whale
+/// buying reputation outright.
+pub const REPUTATION_UNITS_PER_TOKEN: u64 = 100;
+
+/// Decimals of a lamport amount, for counting SOL payouts like token ones
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Units (0.1 SOL or token) a completing payout needs for the full REPUTATION_COMPLETION_POINTS.
+/// Smaller payouts earn a proportional share, so near-empty dares earn next to nothing.
+pub const REPUTATION_FULL_COMPLETION_UNITS: u64 = 10;
+
+/// Challenger reputation at which a daree's credit counts in full
+pub const REPUTATION_TRUSTED_CHALLENGER: i64 = 50;
+
+/// Share of the credit, in basis points, a challenger with no reputation passes on. It grows linearly
+/// up to the full credit at REPUTATION_TRUSTED_CHALLENGER, so fresh wallets can't farm at full rate.
+pub const REPUTATION_NEWCOMER_SHARE_BPS: u64 = 2_500;
+
+/// Reputation lost for a failed dare
+pub const REPUTATION_FAILURE_PENALTY: i64 = 25;
+
+/// Reputation lost by a challenger (or judge) whose proof review ran out and auto-approved
+pub const REPUTATION_GHOSTING_PENALTY: i64 = 15;
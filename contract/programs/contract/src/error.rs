@@ -30,4 +30,126 @@ pub enum DareMeError {
     MissingDareeStats,
     #[msg("This dare does not have a target daree to refuse")]
     NotTargetedDare,
+    #[msg("Token accounts are required for this dare's mint")]
+    MissingTokenAccounts,
+    #[msg("Mint does not match the dare's mint")]
+    InvalidMint,
+    #[msg("Mint has an extension that is not supported for escrow")]
+    UnsupportedMintExtension,
+    #[msg("Mint has a freeze authority and is not on the allowlist")]
+    FreezableMint,
+    #[msg("This dare is settled by community vote")]
+    CommunityVoteDare,
+    #[msg("This dare is not settled by community vote")]
+    NotCommunityVote,
+    #[msg("The voting period has ended")]
+    VotingClosed,
+    #[msg("The voting period has not ended yet")]
+    VotingActive,
+    #[msg("The challenger and daree cannot vote on their own dare")]
+    IneligibleVoter,
+    #[msg("This wallet has already voted on this dare")]
+    AlreadyVoted,
+    #[msg("Submission account is required for this bounty")]
+    MissingSubmission,
+    #[msg("Submission does not belong to this dare")]
+    InvalidSubmission,
+    #[msg("A pending submission cannot be replaced")]
+    SubmissionPending,
+    #[msg("Payout shares must be non-zero, at most 5, and sum to 100%")]
+    InvalidPayoutSchedule,
+    #[msg("Each payout share must cover the vault's rent-exempt minimum")]
+    PayoutShareTooSmall,
+    #[msg("The challenger cannot back their own dare")]
+    CannotBackOwnDare,
+    #[msg("The dare has not been refunded")]
+    DareNotRefunded,
+    #[msg("Backing must cover the vault's rent-exempt minimum")]
+    BackingTooSmall,
+    #[msg("Counter-stakes are not supported on bounties with parallel submissions")]
+    StakeNotSupported,
+    #[msg("Fee exceeds the maximum allowed")]
+    FeeTooHigh,
+    #[msg("Only the config admin can do this")]
+    UnauthorizedAdmin,
+    #[msg("Treasury does not match the config")]
+    InvalidTreasury,
+    #[msg("The program is paused")]
+    ProgramPaused,
+    #[msg("Only the admin or guardian can pause the program")]
+    UnauthorizedGuardian,
+    #[msg("No arbiter is configured")]
+    ArbiterNotSet,
+    #[msg("Only the configured arbiter can resolve disputes")]
+    UnauthorizedArbiter,
+    #[msg("The dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Split must give the daree between 0% and 100%")]
+    InvalidRuling,
+    #[msg("Dare is not in a final status")]
+    DareNotTerminal,
+    #[msg("Backers still have refunds to claim")]
+    RefundsOutstanding,
+    #[msg("Only the challenger or daree can do this")]
+    NotAParty,
+    #[msg("New deadline must be later than the current one")]
+    DeadlineNotExtended,
+    #[msg("There is no pending deadline proposal")]
+    NoPendingProposal,
+    #[msg("The other party has to accept this proposal")]
+    CannotAcceptOwnProposal,
+    #[msg("Streaks need a DirectDare settled by the challenger, 1-64 periods, and time to run them all before the deadline")]
+    InvalidStreak,
+    #[msg("The streak's first check-in period hasn't opened yet")]
+    StreakNotStarted,
+    #[msg("The streak's first check-in period is already over")]
+    StreakStarted,
+    #[msg("Already checked in for this period")]
+    AlreadyCheckedIn,
+    #[msg("Missed periods must be settled through expire_dare first")]
+    MissedPeriodsUnsettled,
+    #[msg("Not supported on this kind of streak dare")]
+    NotSupportedForStreak,
+    #[msg("Only the dare's judge (or the challenger, if it has none) can do this")]
+    UnauthorizedJudge,
+    #[msg("A judge must be a third party on a dare settled by the challenger")]
+    InvalidJudge,
+    #[msg("Judge account is required for this settlement")]
+    MissingJudge,
+    #[msg("No oracle is configured")]
+    OracleNotSet,
+    #[msg("Expected an Ed25519 signature instruction right before this one")]
+    MissingAttestation,
+    #[msg("Attestation is not signed by the oracle over this dare, proof and verdict")]
+    InvalidAttestation,
+    #[msg("Description has already been revealed")]
+    DescriptionAlreadyRevealed,
+    #[msg("Description does not hash to the dare's description_hash")]
+    DescriptionMismatch,
+    #[msg("Description is longer than MAX_DESCRIPTION_LEN bytes")]
+    DescriptionTooLong,
+    #[msg("Proof attempt account is required for this rejection")]
+    MissingProofAttempt,
+    #[msg("Proof attempt is not the one under review")]
+    InvalidProofAttempt,
+    #[msg("Dispute window is outside the allowed range")]
+    InvalidDisputeWindow,
+    #[msg("Time to complete must be positive and at most 30 days")]
+    InvalidTimeToComplete,
+    #[msg("Client-picked dare IDs must be below the range the program assigns from")]
+    InvalidDareId,
+    #[msg("Account uses an older layout; run migrate_dare or migrate_user_stats first")]
+    AccountNotMigrated,
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+    #[msg("Account layout is not recognised")]
+    UnknownAccountLayout,
+    #[msg("Reputation is below this dare's minimum")]
+    ReputationTooLow,
+    #[msg("Dealings account between challenger and daree is required but missing")]
+    MissingDealings,
+    #[msg("The arbiter still has time to rule on this dispute")]
+    ArbiterStillDeciding,
+    #[msg("Daree account is required to return their stake")]
+    MissingDaree,
 }
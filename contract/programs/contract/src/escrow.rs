@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::extension::default_account_state::DefaultAccountState;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeAmount;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::{
+    Account as TokenAccountState, AccountState, Mint as MintState,
+};
+use anchor_spl::token_2022::ID as TOKEN_2022_PROGRAM_ID;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::state::Dare;
+
+/// Escrow held for a single dare.
+/// SOL dares keep lamports directly in the vault PDA.
+/// Token dares keep tokens in the vault PDA's associated token account, with the vault as authority.
+/// Both the legacy token program and Token-2022 are supported through the token interface.
+pub struct Escrow<'a, 'info> {
+    pub dare_key: Pubkey,
+    pub vault_bump: u8,
+    pub vault: &'a SystemAccount<'info>,
+    pub mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    pub vault_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<&'a Interface<'info, TokenInterface>>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl<'a, 'info> Escrow<'a, 'info> {
+    /// Builds the escrow for `dare`, requiring the token accounts when the dare holds a mint.
+    pub fn new(
+        dare: &Account<'info, Dare>,
+        vault: &'a SystemAccount<'info>,
+        mint: Option<&'a InterfaceAccount<'info, Mint>>,
+        vault_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&'a Interface<'info, TokenInterface>>,
+        system_program: &'a Program<'info, System>,
+    ) -> Result<Self> {
+        if dare.has_mint {
+            let mint = mint.ok_or(DareMeError::MissingTokenAccounts)?;
+            require_keys_eq!(mint.key(), dare.mint, DareMeError::InvalidMint);
+            require!(
+                vault_token_account.is_some() && token_program.is_some(),
+                DareMeError::MissingTokenAccounts
+            );
+        }
+
+        Ok(Self {
+            dare_key: dare.key(),
+            vault_bump: dare.vault_bump,
+            vault,
+            mint: if dare.has_mint { mint } else { None },
+            vault_token_account: if dare.has_mint { vault_token_account } else { None },
+            token_program: if dare.has_mint { token_program } else { None },
+            system_program,
+        })
+    }
+
+    /// Decimals of the escrowed amounts: the mint's, or SOL_DECIMALS for lamports
+    pub fn decimals(&self) -> u8 {
+        self.mint.map_or(SOL_DECIMALS, |mint| mint.decimals)
+    }
+
+    /// Everything currently held in escrow, in lamports or base units of the mint.
+    /// Reads the live account data, so it reflects transfers made earlier in the instruction.
+    pub fn balance(&self) -> Result<u64> {
+        match self.vault_token_account {
+            Some(vault_token_account) => {
+                let info = vault_token_account.to_account_info();
+                let data = info.try_borrow_data()?;
+                Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+            }
+            None => Ok(self.vault.lamports()),
+        }
+    }
+
+    /// What to release for a payout of `amount` that may leave part of the escrow behind, capped at
+    /// the balance. A SOL vault can't be left holding less than its rent-exempt minimum, so when the
+    /// leftover would fall below it the payout sweeps the whole vault instead.
+    pub fn releasable(&self, amount: u64) -> Result<u64> {
+        let balance = self.balance()?;
+        if self.mint.is_none() && balance.saturating_sub(amount) < Rent::get()?.minimum_balance(0) {
+            return Ok(balance);
+        }
+        Ok(amount.min(balance))
+    }
+
+    /// Moves `amount` from `from` into escrow.
+    /// Returns what actually landed in escrow, which is less than `amount` for transfer-fee mints.
+    pub fn deposit(
+        &self,
+        from: &Signer<'info>,
+        from_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<u64> {
+        match (self.mint, self.vault_token_account, self.token_program) {
+            (Some(mint), Some(vault_token_account), Some(token_program)) => {
+                let from_token_account = from_token_account.ok_or(DareMeError::MissingTokenAccounts)?;
+                let before = self.balance()?;
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        token_interface::TransferChecked {
+                            from: from_token_account.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: vault_token_account.to_account_info(),
+                            authority: from.to_account_info(),
+                        },
+                    ),
+                    amount,
+                    mint.decimals,
+                )?;
+                let received = self
+                    .balance()?
+                    .checked_sub(before)
+                    .ok_or(DareMeError::ArithmeticOverflow)?;
+                require!(received > 0, DareMeError::InvalidAmount);
+                Ok(received)
+            }
+            _ => {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: from.to_account_info(),
+                            to: self.vault.to_account_info(),
+                        },
+                    ),
+                    amount,
+                )?;
+                Ok(amount)
+            }
+        }
+    }
+
+    /// Moves `amount` out of escrow to `to` (or to `to_token_account` for token dares),
+    /// signing as the vault PDA.
+    pub fn release(
+        &self,
+        to: &AccountInfo<'info>,
+        to_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, self.dare_key.as_ref(), &[self.vault_bump]];
+
+        match (self.mint, self.vault_token_account, self.token_program) {
+            (Some(mint), Some(vault_token_account), Some(token_program)) => {
+                let to_token_account = to_token_account.ok_or(DareMeError::MissingTokenAccounts)?;
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        token_interface::TransferChecked {
+                            from: vault_token_account.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: to_token_account.to_account_info(),
+                            authority: self.vault.to_account_info(),
+                        },
+                        &[vault_seeds],
+                    ),
+                    amount,
+                    mint.decimals,
+                )
+            }
+            _ => system_program::transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.vault.to_account_info(),
+                        to: to.clone(),
+                    },
+                    &[vault_seeds],
+                ),
+                amount,
+            ),
+        }
+    }
+
+    /// Sweeps whatever is left in escrow to `to` and closes the vault's token account,
+    /// returning its rent to `to`. Returns the amount swept.
+    pub fn close(
+        &self,
+        to: &AccountInfo<'info>,
+        to_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    ) -> Result<u64> {
+        let dust = self.balance()?;
+        self.release(to, to_token_account, dust)?;
+
+        if let (Some(vault_token_account), Some(token_program)) = (self.vault_token_account, self.token_program) {
+            // Token-2022 won't close an account holding withheld transfer fees; it stays open until
+            // the mint's withdraw authority harvests them, and only its rent is left behind
+            if has_withheld_fees(vault_token_account)? {
+                return Ok(dust);
+            }
+            let vault_seeds: &[&[u8]] = &[VAULT_SEED, self.dare_key.as_ref(), &[self.vault_bump]];
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_interface::CloseAccount {
+                    account: vault_token_account.to_account_info(),
+                    destination: to.clone(),
+                    authority: self.vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ))?;
+        }
+
+        Ok(dust)
+    }
+}
+
+fn has_withheld_fees(token_account: &InterfaceAccount<TokenAccount>) -> Result<bool> {
+    let info = token_account.to_account_info();
+    if *info.owner != TOKEN_2022_PROGRAM_ID {
+        return Ok(false);
+    }
+
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    Ok(match state.get_extension::<TransferFeeAmount>() {
+        Ok(fee_amount) => u64::from(fee_amount.withheld_amount) > 0,
+        Err(_) => false,
+    })
+}
+
+/// Rejects mints that would let tokens leave escrow or block settlement.
+/// A freeze authority can freeze the vault's token account, so such mints (USDC among them) are only
+/// accepted once the admin allowlists them, trusting the issuer not to freeze escrow.
+/// On Token-2022, a permanent delegate can drain the vault, non-transferable, transfer-hook and pausable
+/// mints can't be paid out by the program, and a frozen default account state freezes the escrow itself.
+pub fn validate_mint(mint: &InterfaceAccount<Mint>, allowlisted: bool) -> Result<()> {
+    require!(mint.freeze_authority.is_none() || allowlisted, DareMeError::FreezableMint);
+
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != TOKEN_2022_PROGRAM_ID {
+        return Ok(());
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    for extension in state.get_extension_types()? {
+        match extension {
+            ExtensionType::PermanentDelegate
+            | ExtensionType::NonTransferable
+            | ExtensionType::TransferHook
+            | ExtensionType::Pausable => {
+                return err!(DareMeError::UnsupportedMintExtension);
+            }
+            ExtensionType::DefaultAccountState => {
+                let default_state = state.get_extension::<DefaultAccountState>()?;
+                require!(
+                    default_state.state != AccountState::Frozen as u8,
+                    DareMeError::UnsupportedMintExtension
+                );
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,413 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{DareStatus, DareType, DisputeRuling, MissPolicy, WinnerSelection};
+
+// ============================================================================
+// Dare lifecycle
+// ============================================================================
+
+/// Emitted when a dare is created and its escrow funded
+#[event]
+pub struct DareCreated {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    /// Pubkey::default() for open dares
+    pub target_daree: Pubkey,
+    pub dare_id: u64,
+    pub description_hash: [u8; 32],
+    pub amount: u64,
+    /// Pubkey::default() for SOL dares
+    pub mint: Pubkey,
+    pub dare_type: DareType,
+    pub winner_selection: WinnerSelection,
+    pub winner_count: u8,
+    pub daree_stake: u64,
+    /// 0 unless this is a streak dare
+    pub streak_periods: u8,
+    pub streak_period: i64,
+    pub miss_policy: MissPolicy,
+    /// Pubkey::default() when the challenger judges
+    pub judge: Pubkey,
+    pub judge_fee_bps: u16,
+    pub dispute_window: i64,
+    /// The accept-by time when `time_to_complete` is set, otherwise the completion deadline
+    pub deadline: i64,
+    /// 0 for an absolute deadline
+    pub time_to_complete: i64,
+    /// 0 when anyone can take the dare
+    pub min_reputation: i64,
+    pub status: DareStatus,
+    pub created_at: i64,
+}
+
+/// Emitted when a daree accepts a DirectDare
+#[event]
+pub struct DareAccepted {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub daree: Pubkey,
+    pub stake: u64,
+    /// Completion deadline, which starts counting here for dares with a relative one
+    pub deadline: i64,
+    pub status: DareStatus,
+    pub accepted_at: i64,
+}
+
+/// Emitted for every proof submission, including re-submissions
+#[event]
+pub struct ProofSubmitted {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub submitter: Pubkey,
+    pub proof_hash: [u8; 32],
+    pub attempt: u32,
+    pub stake: u64,
+    pub status: DareStatus,
+    pub submitted_at: i64,
+}
+
+/// Emitted when the challenger (or judge) rejects a proof (or one bounty submission),
+/// or a community vote rejects it in tally_votes
+#[event]
+pub struct ProofRejected {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub judge: Pubkey,
+    pub submitter: Pubkey,
+    pub proof_hash: [u8; 32],
+    pub attempt: u32,
+    pub reason_hash: [u8; 32],
+    pub status: DareStatus,
+    pub rejected_at: i64,
+}
+
+/// Emitted when the challenger (or judge) approves a proof and a winner is paid,
+/// or a proof stands after a community vote in tally_votes
+#[event]
+pub struct DareApproved {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub judge: Pubkey,
+    pub daree: Pubkey,
+    /// Paid to the daree, after the fees (includes their returned stake)
+    pub amount: u64,
+    pub fee: u64,
+    pub judge_fee: u64,
+    pub winners_paid: u8,
+    pub status: DareStatus,
+    pub approved_at: i64,
+}
+
+/// Emitted when the challenger cancels before anyone accepts
+#[event]
+pub struct DareCancelled {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub refunded: u64,
+    pub status: DareStatus,
+    pub cancelled_at: i64,
+}
+
+/// Emitted when the daree refuses, or gives up after accepting
+#[event]
+pub struct DareRefused {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub daree: Pubkey,
+    /// Paid to the challenger, including any forfeited stake
+    pub refunded: u64,
+    pub forfeited_stake: u64,
+    pub status: DareStatus,
+    pub refused_at: i64,
+}
+
+/// Emitted when an expired dare is refunded to the challenger
+#[event]
+pub struct DareExpired {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    /// Pubkey::default() if nobody took the dare
+    pub daree: Pubkey,
+    /// False when the accept-by time passed with nobody taking the dare
+    pub accepted: bool,
+    /// Paid to the challenger, including any forfeited stake
+    pub refunded: u64,
+    pub forfeited_stake: u64,
+    /// Paid to a judge whose rejection stood
+    pub judge_fee: u64,
+    pub status: DareStatus,
+    pub expired_at: i64,
+}
+
+/// Emitted when an unanswered proof is paid out after the dispute window
+#[event]
+pub struct DareAutoApproved {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub daree: Pubkey,
+    /// Paid to the daree, after the fee (includes their returned stake)
+    pub amount: u64,
+    pub fee: u64,
+    pub winners_paid: u8,
+    pub status: DareStatus,
+    pub approved_at: i64,
+}
+
+/// Emitted when a third party adds to an open dare's escrow
+#[event]
+pub struct DareBacked {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub backer: Pubkey,
+    pub added: u64,
+    /// The backer's running contribution to this dare
+    pub backer_total: u64,
+    pub new_amount: u64,
+    pub status: DareStatus,
+    pub backed_at: i64,
+}
+
+/// Emitted when a backer claims their share of a refunded dare
+#[event]
+pub struct RefundClaimed {
+    pub dare: Pubkey,
+    pub backer: Pubkey,
+    pub refunded: u64,
+    pub backers_refunded: u32,
+    pub claimed_at: i64,
+}
+
+/// Emitted when the challenger adds to an open dare's reward
+#[event]
+pub struct StakeIncreased {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub added: u64,
+    pub new_amount: u64,
+    pub status: DareStatus,
+    pub increased_at: i64,
+}
+
+/// Emitted when the challenger or daree proposes a later deadline
+#[event]
+pub struct DeadlineExtensionProposed {
+    pub dare: Pubkey,
+    pub proposer: Pubkey,
+    pub current_deadline: i64,
+    pub proposed_deadline: i64,
+    pub proposed_at: i64,
+}
+
+/// Emitted when the other party accepts a proposed deadline
+#[event]
+pub struct DeadlineExtended {
+    pub dare: Pubkey,
+    pub proposer: Pubkey,
+    pub accepted_by: Pubkey,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+    pub extended_at: i64,
+}
+
+/// Emitted alongside ProofSubmitted for every streak check-in
+#[event]
+pub struct StreakCheckIn {
+    pub dare: Pubkey,
+    pub daree: Pubkey,
+    pub period: u8,
+    pub checkins: u64,
+    pub status: DareStatus,
+    pub checked_in_at: i64,
+}
+
+/// Emitted when the expire_dare crank settles missed streak periods without ending the dare
+#[event]
+pub struct StreakPeriodsMissed {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub daree: Pubkey,
+    pub missed: u8,
+    pub total_missed: u8,
+    pub forfeited: u64,
+    pub status: DareStatus,
+    pub timestamp: i64,
+}
+
+/// Emitted when the oracle's signed verdict settles a proof in place of the challenger
+#[event]
+pub struct ProofAttested {
+    pub dare: Pubkey,
+    pub oracle: Pubkey,
+    pub submitter: Pubkey,
+    pub proof_hash: [u8; 32],
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted once the plaintext description is checked against description_hash on-chain
+#[event]
+pub struct DescriptionRevealed {
+    pub dare: Pubkey,
+    pub revealed_by: Pubkey,
+    pub description_hash: [u8; 32],
+    pub description: String,
+    pub revealed_at: i64,
+}
+
+// ============================================================================
+// Disputes and closing
+// ============================================================================
+
+/// Emitted when a daree escalates a rejected proof to the arbiter
+#[event]
+pub struct DisputeOpened {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub daree: Pubkey,
+    pub proof_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when the arbiter rules on a dispute
+#[event]
+pub struct DisputeResolved {
+    pub dare: Pubkey,
+    pub arbiter: Pubkey,
+    pub ruling: DisputeRuling,
+    pub to_daree: u64,
+    pub to_challenger: u64,
+    pub fee: u64,
+    pub judge_fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a dispute lapses because the arbiter never ruled within ARBITER_TIMEOUT
+#[event]
+pub struct DisputeLapsed {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub daree: Pubkey,
+    /// The daree's own stake, returned
+    pub to_daree: u64,
+    /// The reward refunded to the challenger (backers claim theirs separately)
+    pub to_challenger: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a terminal dare's accounts are closed.
+/// Carries the final state so the dare can be rebuilt off-chain once the account is gone.
+#[event]
+pub struct DareClosed {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub daree: Pubkey,
+    pub dare_id: u64,
+    pub description_hash: [u8; 32],
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub status: DareStatus,
+    pub dare_type: DareType,
+    pub winner_selection: WinnerSelection,
+    pub proof_hash: [u8; 32],
+    pub created_at: i64,
+    pub deadline: i64,
+    pub accepted_at: i64,
+    pub completed_at: i64,
+    pub backed_amount: u64,
+    pub winners_paid: u8,
+    pub judge: Pubkey,
+    pub checkins: u64,
+    pub missed_periods: u8,
+    pub dust_swept: u64,
+    pub closed_at: i64,
+}
+
+// ============================================================================
+// Admin
+// ============================================================================
+
+/// Emitted when the admin or guardian pauses the program
+#[event]
+pub struct ProgramPaused {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin or guardian lifts the pause
+#[event]
+pub struct ProgramUnpaused {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets or clears the guardian key
+#[event]
+pub struct GuardianUpdated {
+    pub admin: Pubkey,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin adds a freezable mint to the escrow allowlist or removes it
+#[event]
+pub struct MintAllowlistUpdated {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub allowed: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin changes the protocol fee
+#[event]
+pub struct FeeUpdated {
+    pub admin: Pubkey,
+    pub old_fee_bps: u16,
+    pub fee_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin points fee collection at a new treasury
+#[event]
+pub struct TreasuryUpdated {
+    pub admin: Pubkey,
+    pub old_treasury: Pubkey,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the key that resolves disputes
+#[event]
+pub struct ArbiterUpdated {
+    pub admin: Pubkey,
+    pub old_arbiter: Pubkey,
+    pub arbiter: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets (or clears) the attestation oracle
+#[event]
+pub struct OracleUpdated {
+    pub admin: Pubkey,
+    pub old_oracle: Pubkey,
+    pub oracle: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin changes the range of dispute windows new dares can pick
+#[event]
+pub struct DisputeWindowBoundsUpdated {
+    pub admin: Pubkey,
+    pub min_window: i64,
+    pub max_window: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a Dare or UserStats account is moved onto the current layout
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub payer: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::error::DareMeError;
+use crate::events::*;
+use crate::escrow::Escrow;
 use crate::state::*;
 
 pub fn handler(ctx: Context<AcceptDare>) -> Result<()> {
@@ -12,6 +15,7 @@ pub fn handler(ctx: Context<AcceptDare>) -> Result<()> {
     require!(dare.dare_type == DareType::DirectDare, DareMeError::InvalidDareType);
     require!(dare.status == DareStatus::Created, DareMeError::InvalidDareStatus);
     require!(ctx.accounts.daree.key() != dare.challenger, DareMeError::CannotAcceptOwnDare);
+    require!(!dare.has_judge || ctx.accounts.daree.key() != dare.judge, DareMeError::InvalidJudge);
     require!(dare.deadline > now, DareMeError::DareExpired);
 
     // If dare has a target, only that person can accept
@@ -21,23 +25,65 @@ pub fn handler(ctx: Context<AcceptDare>) -> Result<()> {
             DareMeError::UnauthorizedDaree
         );
     }
+    require!(
+        ctx.accounts.daree_stats.meets_reputation(dare.min_reputation),
+        DareMeError::ReputationTooLow
+    );
 
+    // A streak can't be joined once its first check-in period has gone by
+    if dare.is_streak() {
+        require!(now < dare.streak_start() + dare.streak_period, DareMeError::StreakStarted);
+    }
+
+    // Lock the daree's counter-stake in the same escrow as the reward
+    if dare.stake_due() {
+        let stake = dare.daree_stake;
+        let escrow = Escrow::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.vault,
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        let received = escrow.deposit(
+            &ctx.accounts.daree,
+            ctx.accounts.daree_token_account.as_ref(),
+            stake,
+        )?;
+        ctx.accounts.dare.stake_held = received;
+    }
+
+    let dare = &mut ctx.accounts.dare;
     dare.daree = ctx.accounts.daree.key();
     dare.has_daree = true;
     dare.status = DareStatus::Active;
-    dare.accepted_at = now;
+    dare.start_completion_clock(now)?;
 
     let stats = &mut ctx.accounts.daree_stats;
     if stats.user == Pubkey::default() {
         stats.user = ctx.accounts.daree.key();
         stats.bump = ctx.bumps.daree_stats;
+        stats.version = USER_STATS_VERSION;
     }
     stats.dares_accepted += 1;
 
-    msg!("Dare accepted by: {}", ctx.accounts.daree.key());
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(DareAccepted {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        daree: dare.daree,
+        stake: dare.stake_held,
+        deadline: dare.deadline,
+        status: dare.status,
+        accepted_at: now,
+    });
+
+    msg!("Dare accepted by: {}, stake={}", ctx.accounts.daree.key(), ctx.accounts.dare.stake_held);
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct AcceptDare<'info> {
     #[account(mut)]
@@ -47,17 +93,58 @@ pub struct AcceptDare<'info> {
         mut,
         seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
         bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub dare: Account<'info, Dare>,
 
+    /// Blocks new money from entering while the program is paused
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ DareMeError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         init_if_needed,
         payer = daree,
         space = UserStats::SPACE,
         seeds = [USER_STATS_SEED, daree.key().as_ref()],
         bump,
+        constraint = daree_stats.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub daree_stats: Account<'info, UserStats>,
 
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Optional: only for token dares with a counter-stake
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: daree's token account funding the counter-stake
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = daree,
+        token::token_program = token_program,
+    )]
+    pub daree_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
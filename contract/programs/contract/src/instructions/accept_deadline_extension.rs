@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// The other party accepts a pending deadline proposal, which becomes the new deadline
+pub fn handler(ctx: Context<AcceptDeadlineExtension>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let accepter = ctx.accounts.accepter.key();
+
+    let dare = &mut ctx.accounts.dare;
+    require!(
+        dare.status == DareStatus::Active || dare.status == DareStatus::Rejected,
+        DareMeError::InvalidDareStatus
+    );
+    require!(dare.proposed_deadline != 0, DareMeError::NoPendingProposal);
+    require!(dare.is_party(&accepter), DareMeError::NotAParty);
+    require!(accepter != dare.proposed_by, DareMeError::CannotAcceptOwnProposal);
+    require!(dare.deadline > now, DareMeError::DareExpired);
+
+    let old_deadline = dare.deadline;
+    let proposer = dare.proposed_by;
+    dare.deadline = dare.proposed_deadline;
+    dare.proposed_deadline = 0;
+    dare.proposed_by = Pubkey::default();
+
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(DeadlineExtended {
+        dare: dare.key(),
+        proposer,
+        accepted_by: accepter,
+        old_deadline,
+        new_deadline: dare.deadline,
+        extended_at: now,
+    });
+
+    msg!("Dare {} deadline extended from {} to {}", dare.dare_id, old_deadline, dare.deadline);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptDeadlineExtension<'info> {
+    pub accepter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+}
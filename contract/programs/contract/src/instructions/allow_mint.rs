@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin accepts a mint with a freeze authority for escrow (e.g. USDC). Only affects new dares.
+pub fn handler(ctx: Context<AllowMint>) -> Result<()> {
+    let allowed_mint = &mut ctx.accounts.allowed_mint;
+    allowed_mint.mint = ctx.accounts.mint.key();
+    allowed_mint.bump = ctx.bumps.allowed_mint;
+
+    emit_cpi!(MintAllowlistUpdated {
+        admin: ctx.accounts.admin.key(),
+        mint: allowed_mint.mint,
+        allowed: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Mint allowlisted: {}", allowed_mint.mint);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AllowMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AllowedMint::SPACE,
+        seeds = [ALLOWED_MINT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,64 +1,155 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::error::DareMeError;
+use crate::events::*;
+use crate::escrow::Escrow;
 use crate::state::*;
 
 pub fn handler(ctx: Context<ApproveDare>) -> Result<()> {
     // Capture values before mutable borrow
-    let dare_key = ctx.accounts.dare.key();
-    let vault_bump = ctx.accounts.dare.vault_bump;
-    let dare_amount = ctx.accounts.dare.amount;
+    let has_mint = ctx.accounts.dare.has_mint;
     let dare_id = ctx.accounts.dare.dare_id;
 
     let dare = &mut ctx.accounts.dare;
     require!(dare.status == DareStatus::ProofSubmitted, DareMeError::InvalidDareStatus);
+    require!(
+        dare.winner_selection == WinnerSelection::ChallengerSelect,
+        DareMeError::CommunityVoteDare
+    );
 
-    let clock = Clock::get()?;
-    dare.status = DareStatus::Completed;
-    dare.completed_at = clock.unix_timestamp;
-
-    // Transfer all SOL from vault to daree using CPI with PDA signer
-    let vault_seeds: &[&[u8]] = &[VAULT_SEED, dare_key.as_ref(), &[vault_bump]];
-    let vault_lamports = ctx.accounts.vault.lamports();
-
-    system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.daree.to_account_info(),
-            },
-            &[vault_seeds],
-        ),
-        vault_lamports,
+    // Bounties with parallel submissions: the approved submission's submitter becomes the daree
+    if dare.takes_submissions() {
+        let submission = ctx.accounts.submission.as_mut().ok_or(DareMeError::MissingSubmission)?;
+        dare.award_submission(submission)?;
+    } else {
+        require!(
+            dare.has_daree && ctx.accounts.daree.key() == dare.daree,
+            DareMeError::UnauthorizedDaree
+        );
+    }
+
+    // Transfer the daree's share of escrow from vault to daree using CPI with PDA signer
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
     )?;
+    let released = ctx.accounts.dare.next_payout(escrow.balance()?)?;
+
+    // The protocol and judge fees come out of the reward; the daree's own counter-stake is returned untouched
+    let reward = ctx.accounts.dare.reward_portion(released);
+    let fee = ctx.accounts.config.fee_on(reward)?;
+    let judge_fee = ctx.accounts.dare.judge_fee_on(reward)?;
+    escrow.release(
+        &ctx.accounts.treasury.to_account_info(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        fee,
+    )?;
+    escrow.release(
+        &ctx.accounts.authority.to_account_info(),
+        ctx.accounts.authority_token_account.as_ref(),
+        judge_fee,
+    )?;
+    let paid = released - fee - judge_fee;
+    escrow.release(
+        &ctx.accounts.daree.to_account_info(),
+        ctx.accounts.daree_token_account.as_ref(),
+        paid,
+    )?;
+    let earned = reward - fee - judge_fee;
+    let decimals = escrow.decimals();
+
+    let clock = Clock::get()?;
+    let dare = &mut ctx.accounts.dare;
+    dare.record_payout(clock.unix_timestamp);
 
     // Update daree stats
+    let dealings = &mut ctx.accounts.dealings;
+    dealings.open(&dare.challenger, &dare.daree, ctx.bumps.dealings);
     let daree_stats = &mut ctx.accounts.daree_stats;
-    daree_stats.dares_completed += 1;
-    daree_stats.total_earned = daree_stats.total_earned
-        .checked_add(dare_amount)
-        .ok_or(DareMeError::ArithmeticOverflow)?;
+    if dare.is_milestone_dare() {
+        daree_stats.milestones_completed += 1;
+    }
+    if dare.payout_completes_dare() {
+        daree_stats.dares_completed += 1;
+    }
+    if !has_mint {
+        daree_stats.total_earned = daree_stats.total_earned
+            .checked_add(earned)
+            .ok_or(DareMeError::ArithmeticOverflow)?;
+    }
+    daree_stats.credit_reputation(dare, earned, decimals, dare.payout_completes_dare(), dealings);
+
+    // Update judge stats
+    if dare.has_judge {
+        let judge_stats = &mut ctx.accounts.authority_stats;
+        if judge_stats.user == Pubkey::default() {
+            judge_stats.user = ctx.accounts.authority.key();
+            judge_stats.bump = ctx.bumps.authority_stats;
+            judge_stats.version = USER_STATS_VERSION;
+        }
+        judge_stats.dares_judged += 1;
+        if !has_mint {
+            judge_stats.judge_fees_earned = judge_stats.judge_fees_earned
+                .checked_add(judge_fee)
+                .ok_or(DareMeError::ArithmeticOverflow)?;
+        }
+    }
 
-    msg!("Dare {} approved! {} lamports released.", dare_id, vault_lamports);
+    emit_cpi!(DareApproved {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        judge: dare.judge,
+        daree: dare.daree,
+        amount: paid,
+        fee,
+        judge_fee,
+        winners_paid: dare.winners_paid,
+        status: dare.status,
+        approved_at: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Dare {} approved! {} released to winner {}/{} ({} fee, {} judge fee).",
+        dare_id, paid, dare.winners_paid, dare.winner_count, fee, judge_fee
+    );
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ApproveDare<'info> {
-    #[account(mut)]
-    pub challenger: Signer<'info>,
+    /// The challenger, or the judge when the dare has one
+    #[account(
+        mut,
+        constraint = dare.can_judge(&authority.key()) @ DareMeError::UnauthorizedJudge,
+    )]
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
         seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
         bump = dare.bump,
-        has_one = challenger,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub dare: Account<'info, Dare>,
 
+    /// Only updated when the authority is a judge
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserStats::SPACE,
+        seeds = [USER_STATS_SEED, authority.key().as_ref()],
+        bump,
+        constraint = authority_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub authority_stats: Account<'info, UserStats>,
+
     /// CHECK: Vault PDA validated by seeds
     #[account(
         mut,
@@ -67,19 +158,91 @@ pub struct ApproveDare<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
-    /// CHECK: Must be the daree stored in the dare account
-    #[account(
-        mut,
-        constraint = dare.has_daree && daree.key() == dare.daree @ DareMeError::UnauthorizedDaree,
-    )]
+    /// CHECK: Must be the daree stored in the dare account (or the submitter being approved) — validated in handler
+    #[account(mut)]
     pub daree: SystemAccount<'info>,
 
     #[account(
         mut,
         seeds = [USER_STATS_SEED, daree.key().as_ref()],
         bump = daree_stats.bump,
+        constraint = daree_stats.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub daree_stats: Account<'info, UserStats>,
 
+    /// Optional: only needed for PublicBounty dares settled by the challenger
+    #[account(
+        mut,
+        seeds = [SUBMISSION_SEED, dare.key().as_ref(), daree.key().as_ref()],
+        bump = submission.bump,
+    )]
+    pub submission: Option<Account<'info, Submission>>,
+
+    /// Dares the challenger and daree have completed together, which discounts the daree's reputation credit
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Dealings::SPACE,
+        seeds = [
+            DEALINGS_SEED,
+            Dealings::lower(&dare.challenger, daree.key),
+            Dealings::higher(&dare.challenger, daree.key),
+        ],
+        bump,
+    )]
+    pub dealings: Account<'info, Dealings>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = treasury @ DareMeError::InvalidTreasury,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury receiving the fee — validated via has_one on config
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: daree's token account receiving the payout
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = daree,
+        token::token_program = token_program,
+    )]
+    pub daree_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: judge's token account receiving the judge fee on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: treasury's token account receiving the fee on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::escrow::Escrow;
+use crate::events::*;
+use crate::state::*;
+
+/// Any wallet other than the challenger adds funds to an open dare's escrow.
+/// Contributions are recorded per backer so refunds can be returned pro-rata.
+pub fn handler(ctx: Context<BackDare>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let dare = &ctx.accounts.dare;
+    require!(amount > 0, DareMeError::InvalidAmount);
+    require!(
+        dare.status == DareStatus::Created
+            || dare.status == DareStatus::Active
+            || dare.status == DareStatus::Rejected,
+        DareMeError::InvalidDareStatus
+    );
+    require!(dare.deadline > now, DareMeError::DareExpired);
+    require!(ctx.accounts.backer.key() != dare.challenger, DareMeError::CannotBackOwnDare);
+    // Forfeited streak slices go straight to the challenger, which would shortchange backers' refunds
+    require!(
+        !(dare.is_streak() && dare.miss_policy == MissPolicy::ForfeitSlice),
+        DareMeError::NotSupportedForStreak
+    );
+
+    // SOL vaults must stay rent-exempt while refunds are claimed one backer at a time
+    if !dare.has_mint {
+        require!(
+            amount >= Rent::get()?.minimum_balance(0),
+            DareMeError::BackingTooSmall
+        );
+    }
+
+    // Transfer SOL (or tokens) from backer to vault
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let received = escrow.deposit(
+        &ctx.accounts.backer,
+        ctx.accounts.backer_token_account.as_ref(),
+        amount,
+    )?;
+
+    let dare = &mut ctx.accounts.dare;
+    let backer_account = &mut ctx.accounts.backer_account;
+    // A backing older than the dare belongs to a closed dare that used the same address
+    if backer_account.backer == Pubkey::default() || backer_account.backed_at < dare.created_at {
+        backer_account.dare = dare.key();
+        backer_account.backer = ctx.accounts.backer.key();
+        backer_account.bump = ctx.bumps.backer_account;
+        backer_account.amount = 0;
+        dare.backer_count = dare.backer_count.checked_add(1).ok_or(DareMeError::ArithmeticOverflow)?;
+    }
+    backer_account.amount = backer_account.amount
+        .checked_add(received)
+        .ok_or(DareMeError::ArithmeticOverflow)?;
+    backer_account.backed_at = now;
+
+    dare.amount = dare.amount.checked_add(received).ok_or(DareMeError::ArithmeticOverflow)?;
+    dare.backed_amount = dare.backed_amount.checked_add(received).ok_or(DareMeError::ArithmeticOverflow)?;
+
+    emit_cpi!(DareBacked {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        backer: ctx.accounts.backer.key(),
+        added: received,
+        backer_total: backer_account.amount,
+        new_amount: dare.amount,
+        status: dare.status,
+        backed_at: now,
+    });
+
+    msg!("Dare {} backed by {} with {}. Pot is now {}.", dare.dare_id, ctx.accounts.backer.key(), received, dare.amount);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BackDare<'info> {
+    #[account(mut)]
+    pub backer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    /// Blocks new money from entering while the program is paused
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ DareMeError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = backer,
+        space = Backer::SPACE,
+        seeds = [BACKER_SEED, dare.key().as_ref(), backer.key().as_ref()],
+        bump,
+    )]
+    pub backer_account: Account<'info, Backer>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: backer's token account funding the escrow
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = backer,
+        token::token_program = token_program,
+    )]
+    pub backer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
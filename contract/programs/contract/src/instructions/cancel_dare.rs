@@ -1,45 +1,57 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::error::DareMeError;
+use crate::events::*;
+use crate::escrow::Escrow;
 use crate::state::*;
 
 pub fn handler(ctx: Context<CancelDare>) -> Result<()> {
     // Capture values before mutable borrow
-    let dare_key = ctx.accounts.dare.key();
-    let vault_bump = ctx.accounts.dare.vault_bump;
-    let dare_amount = ctx.accounts.dare.amount;
+    let has_mint = ctx.accounts.dare.has_mint;
     let dare_id = ctx.accounts.dare.dare_id;
 
     let dare = &mut ctx.accounts.dare;
     require!(dare.status == DareStatus::Created, DareMeError::InvalidDareStatus);
     dare.status = DareStatus::Cancelled;
 
-    // Refund SOL from vault to challenger using CPI with PDA signer
-    let vault_seeds: &[&[u8]] = &[VAULT_SEED, dare_key.as_ref(), &[vault_bump]];
-    let vault_lamports = ctx.accounts.vault.lamports();
-
-    system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.challenger.to_account_info(),
-            },
-            &[vault_seeds],
-        ),
-        vault_lamports,
+    // Refund the challenger's share of escrow from vault using CPI with PDA signer
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let refunded = escrow.releasable(ctx.accounts.dare.start_refund(escrow.balance()?)?)?;
+    escrow.release(
+        &ctx.accounts.challenger.to_account_info(),
+        ctx.accounts.challenger_token_account.as_ref(),
+        refunded,
     )?;
 
     // Update stats
-    let stats = &mut ctx.accounts.challenger_stats;
-    stats.total_spent = stats.total_spent.saturating_sub(dare_amount);
+    if !has_mint {
+        let stats = &mut ctx.accounts.challenger_stats;
+        stats.total_spent = stats.total_spent.saturating_sub(refunded);
+    }
+
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(DareCancelled {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        refunded,
+        status: dare.status,
+        cancelled_at: Clock::get()?.unix_timestamp,
+    });
 
-    msg!("Dare {} cancelled. {} lamports refunded.", dare_id, vault_lamports);
+    msg!("Dare {} cancelled. {} refunded.", dare_id, refunded);
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CancelDare<'info> {
     #[account(mut)]
@@ -50,6 +62,7 @@ pub struct CancelDare<'info> {
         seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
         bump = dare.bump,
         has_one = challenger,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub dare: Account<'info, Dare>,
 
@@ -65,8 +78,32 @@ pub struct CancelDare<'info> {
         mut,
         seeds = [USER_STATS_SEED, challenger.key().as_ref()],
         bump = challenger_stats.bump,
+        constraint = challenger_stats.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub challenger_stats: Account<'info, UserStats>,
 
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: challenger's token account receiving the payout
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = challenger,
+        token::token_program = token_program,
+    )]
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
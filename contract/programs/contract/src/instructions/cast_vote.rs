@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::state::*;
+
+/// Any wallet other than the challenger or daree casts one vote on a CommunityVote proof.
+/// The Vote PDA is seeded by voter, so each wallet votes once; a Vote older than the dare
+/// belongs to a closed dare that used the same address and is overwritten.
+pub fn handler(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+    let dare = &mut ctx.accounts.dare;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    require!(
+        dare.winner_selection == WinnerSelection::CommunityVote,
+        DareMeError::NotCommunityVote
+    );
+    require!(dare.status == DareStatus::ProofSubmitted, DareMeError::InvalidDareStatus);
+    require!(now < dare.voting_ends_at, DareMeError::VotingClosed);
+    require!(
+        ctx.accounts.voter.key() != dare.challenger && ctx.accounts.voter.key() != dare.daree,
+        DareMeError::IneligibleVoter
+    );
+    let vote = &ctx.accounts.vote;
+    require!(
+        vote.voter == Pubkey::default() || vote.voted_at < dare.created_at,
+        DareMeError::AlreadyVoted
+    );
+
+    if approve {
+        dare.votes_for = dare.votes_for.checked_add(1).ok_or(DareMeError::ArithmeticOverflow)?;
+    } else {
+        dare.votes_against = dare.votes_against.checked_add(1).ok_or(DareMeError::ArithmeticOverflow)?;
+    }
+
+    let vote = &mut ctx.accounts.vote;
+    vote.dare = dare.key();
+    vote.voter = ctx.accounts.voter.key();
+    vote.approve = approve;
+    vote.voted_at = now;
+    vote.bump = ctx.bumps.vote;
+
+    msg!("Vote on dare {} by {}: approve={}", dare.dare_id, ctx.accounts.voter.key(), approve);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = Vote::SPACE,
+        seeds = [VOTE_SEED, dare.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote: Account<'info, Vote>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::escrow::Escrow;
+use crate::events::*;
+use crate::state::*;
+
+/// A backer claims their pro-rata share of a refunded dare's escrow.
+/// The last backer to claim also sweeps any rounding dust, leaving the vault empty. So does a claim that
+/// would leave a SOL vault below rent exemption, and later claims get whatever is left.
+/// The Backer account is closed and its rent returned to the backer.
+/// A Backer left over from a closed dare at the same address has no claim and is just closed.
+pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
+    let dare_id = ctx.accounts.dare.dare_id;
+    if ctx.accounts.backer_account.backed_at < ctx.accounts.dare.created_at {
+        msg!("Dare {} stale backing by {} closed without a refund", dare_id, ctx.accounts.backer.key());
+        return Ok(());
+    }
+    require!(ctx.accounts.dare.is_refunded(), DareMeError::DareNotRefunded);
+
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+
+    let dare = &mut ctx.accounts.dare;
+    let is_last = dare.backers_refunded.saturating_add(1) >= dare.backer_count;
+    let refunded = if is_last {
+        escrow.balance()?
+    } else {
+        escrow.releasable(dare.refund_share(ctx.accounts.backer_account.amount)?)?
+    };
+
+    // Refund from vault to backer using CPI with PDA signer
+    escrow.release(
+        &ctx.accounts.backer.to_account_info(),
+        ctx.accounts.backer_token_account.as_ref(),
+        refunded,
+    )?;
+    dare.backers_refunded = dare.backers_refunded.saturating_add(1);
+
+    emit_cpi!(RefundClaimed {
+        dare: dare.key(),
+        backer: ctx.accounts.backer.key(),
+        refunded,
+        backers_refunded: dare.backers_refunded,
+        claimed_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Dare {} refund claimed by backer {}: {}", dare_id, ctx.accounts.backer.key(), refunded);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub backer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = backer,
+        seeds = [BACKER_SEED, dare.key().as_ref(), backer.key().as_ref()],
+        bump = backer_account.bump,
+        has_one = backer,
+    )]
+    pub backer_account: Account<'info, Backer>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: backer's token account receiving the refund
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = backer,
+        token::token_program = token_program,
+    )]
+    pub backer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::escrow::Escrow;
+use crate::events::*;
+use crate::state::*;
+
+/// Closes a finished dare: sweeps any dust left in the vault and returns the rent of the
+/// Dare account (and the vault's token account) to the challenger who paid it.
+/// Permissionless, so anyone can clean up; funds only ever go to the challenger.
+/// Emits DareClosed with the final state so the dare stays reconstructable from events.
+pub fn handler(ctx: Context<CloseDare>) -> Result<()> {
+    let clock = Clock::get()?;
+    let dare = &ctx.accounts.dare;
+
+    // Submissions, backings and votes left behind are recognised as stale by a dare recreated at this address
+    require!(dare.is_terminal(), DareMeError::DareNotTerminal);
+    // Backers claim against the Dare account, so it has to outlive every claim
+    if dare.is_refunded() {
+        require!(dare.backers_refunded >= dare.backer_count, DareMeError::RefundsOutstanding);
+    }
+
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let dust_swept = escrow.close(
+        &ctx.accounts.challenger.to_account_info(),
+        ctx.accounts.challenger_token_account.as_ref(),
+    )?;
+
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(DareClosed {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        daree: dare.daree,
+        dare_id: dare.dare_id,
+        description_hash: dare.description_hash,
+        amount: dare.amount,
+        mint: dare.mint,
+        status: dare.status,
+        dare_type: dare.dare_type,
+        winner_selection: dare.winner_selection,
+        proof_hash: dare.proof_hash,
+        created_at: dare.created_at,
+        deadline: dare.deadline,
+        accepted_at: dare.accepted_at,
+        completed_at: dare.completed_at,
+        backed_amount: dare.backed_amount,
+        winners_paid: dare.winners_paid,
+        judge: dare.judge,
+        checkins: dare.checkins,
+        missed_periods: dare.missed_periods,
+        dust_swept,
+        closed_at: clock.unix_timestamp,
+    });
+
+    msg!("Dare {} closed. {} dust swept to challenger.", dare.dare_id, dust_swept);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseDare<'info> {
+    /// Anyone can call this (permissionless crank)
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        close = challenger,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: The challenger who paid the rent — receives it back along with any dust
+    #[account(
+        mut,
+        constraint = challenger.key() == dare.challenger @ DareMeError::UnauthorizedChallenger,
+    )]
+    pub challenger: SystemAccount<'info>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account, closed here
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: challenger's token account receiving any leftover tokens
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = challenger,
+        token::token_program = token_program,
+    )]
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
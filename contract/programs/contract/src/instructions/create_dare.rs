@@ -1,10 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::error::DareMeError;
+use crate::events::*;
+use crate::escrow::{validate_mint, Escrow};
 use crate::state::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateDare>,
     dare_id: u64,
@@ -13,11 +17,30 @@ pub fn handler(
     deadline: i64,
     dare_type: DareType,
     winner_selection: WinnerSelection,
-    target_daree: Pubkey,
-) -> Result<()> {
+    params: CreateDareParams,
+) -> Result<u64> {
+    let CreateDareParams {
+        target_daree,
+        payout_shares_bps,
+        daree_stake,
+        streak_period,
+        streak_periods,
+        miss_policy,
+        judge,
+        judge_fee_bps,
+        dispute_window,
+        time_to_complete,
+        min_reputation,
+    } = params;
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
 
+    // 0 asks for the next ID from the challenger's counter; explicit IDs are still accepted
+    // for older clients, as long as they stay out of the assigned range
+    require!(dare_id < ASSIGNED_DARE_ID_FLAG, DareMeError::InvalidDareId);
+    let assigned = dare_id == 0;
+    let dare_id = ctx.accounts.challenger_stats.dare_id_for(dare_id);
+
     require!(amount > 0, DareMeError::InvalidAmount);
     require!(deadline > now, DareMeError::DeadlinePassed);
     require!(deadline <= now + MAX_DEADLINE_DURATION, DareMeError::DeadlineTooFar);
@@ -28,6 +51,91 @@ pub fn handler(
         require!(target_daree != ctx.accounts.challenger.key(), DareMeError::CannotAcceptOwnDare);
     }
 
+    // Payout schedule: empty means a single winner takes everything.
+    // Several shares (e.g. 5000/3000/2000) are paid in approval order and must sum to 100%:
+    // one per winner on a bounty, or one per milestone on a DirectDare.
+    let mut payout_bps = [0u16; MAX_WINNERS];
+    if payout_shares_bps.is_empty() {
+        payout_bps[0] = BPS_DENOMINATOR as u16;
+    } else {
+        require!(payout_shares_bps.len() <= MAX_WINNERS, DareMeError::InvalidPayoutSchedule);
+        require!(payout_shares_bps.iter().all(|&bps| bps > 0), DareMeError::InvalidPayoutSchedule);
+        let total_bps: u64 = payout_shares_bps.iter().map(|&bps| bps as u64).sum();
+        require!(total_bps == BPS_DENOMINATOR, DareMeError::InvalidPayoutSchedule);
+        payout_bps[..payout_shares_bps.len()].copy_from_slice(&payout_shares_bps);
+    }
+    let winner_count = payout_shares_bps.len().max(1) as u8;
+
+    // Staged payouts are approved by the challenger; a community vote settles the whole escrow at once
+    if winner_count > 1 {
+        require!(
+            winner_selection == WinnerSelection::ChallengerSelect,
+            DareMeError::InvalidPayoutSchedule
+        );
+    }
+
+    // A counter-stake binds a single daree; parallel bounty submitters have no one stake to forfeit
+    if daree_stake > 0 {
+        require!(
+            !(dare_type == DareType::PublicBounty && winner_selection == WinnerSelection::ChallengerSelect),
+            DareMeError::StakeNotSupported
+        );
+    }
+
+    // Streaks: one check-in per period, for `streak_periods` back-to-back periods ending at the deadline
+    if streak_periods > 0 {
+        require!(
+            dare_type == DareType::DirectDare
+                && winner_selection == WinnerSelection::ChallengerSelect
+                && winner_count == 1,
+            DareMeError::InvalidStreak
+        );
+        require!(streak_periods <= MAX_STREAK_PERIODS, DareMeError::InvalidStreak);
+        require!(streak_period > 0, DareMeError::InvalidStreak);
+        let streak_length = streak_period
+            .checked_mul(streak_periods as i64)
+            .ok_or(DareMeError::ArithmeticOverflow)?;
+        require!(deadline - streak_length >= now, DareMeError::InvalidStreak);
+    }
+    let streak_period = if streak_periods > 0 { streak_period } else { 0 };
+
+    // With a relative completion time, `deadline` only bounds acceptance and the clock starts on accept
+    if time_to_complete != 0 {
+        require!(
+            time_to_complete > 0 && time_to_complete <= MAX_DEADLINE_DURATION,
+            DareMeError::InvalidTimeToComplete
+        );
+        // Streak periods are anchored to a fixed deadline
+        require!(streak_periods == 0, DareMeError::NotSupportedForStreak);
+    }
+
+    // A judge approves or rejects proofs instead of the challenger, for a cut of the reward
+    let has_judge = judge != Pubkey::default();
+    if has_judge {
+        require!(
+            winner_selection == WinnerSelection::ChallengerSelect,
+            DareMeError::InvalidJudge
+        );
+        require!(judge != ctx.accounts.challenger.key(), DareMeError::InvalidJudge);
+        require!(!has_target || judge != target_daree, DareMeError::InvalidJudge);
+        require!(judge_fee_bps <= MAX_JUDGE_FEE_BPS, DareMeError::FeeTooHigh);
+    } else {
+        require!(judge_fee_bps == 0, DareMeError::InvalidJudge);
+    }
+
+    // How long each proof stays under review before expire_dare can auto-approve it.
+    // 0 takes the default, clamped into the range the config allows.
+    let config = &ctx.accounts.config;
+    let dispute_window = if dispute_window == 0 {
+        DISPUTE_WINDOW.clamp(config.min_dispute_window, config.max_dispute_window)
+    } else {
+        dispute_window
+    };
+    require!(
+        dispute_window >= config.min_dispute_window && dispute_window <= config.max_dispute_window,
+        DareMeError::InvalidDisputeWindow
+    );
+
     let dare = &mut ctx.accounts.dare;
     dare.challenger = ctx.accounts.challenger.key();
     dare.dare_id = dare_id;
@@ -44,6 +152,41 @@ pub fn handler(
     dare.completed_at = 0;
     dare.bump = ctx.bumps.dare;
     dare.vault_bump = ctx.bumps.vault;
+    dare.voting_ends_at = 0;
+    dare.votes_for = 0;
+    dare.votes_against = 0;
+    dare.pending_submissions = 0;
+    dare.payout_bps = payout_bps;
+    dare.winner_count = winner_count;
+    dare.winners_paid = 0;
+    dare.backed_amount = 0;
+    dare.backer_count = 0;
+    dare.backers_refunded = 0;
+    dare.refund_pool = 0;
+    dare.daree_stake = daree_stake;
+    dare.stake_held = 0;
+    dare.disputed_at = 0;
+    dare.proposed_deadline = 0;
+    dare.proposed_by = Pubkey::default();
+    dare.streak_period = streak_period;
+    dare.streak_periods = streak_periods;
+    dare.miss_policy = miss_policy;
+    dare.checkins = 0;
+    dare.periods_settled = 0;
+    dare.missed_periods = 0;
+    dare.judge = judge;
+    dare.has_judge = has_judge;
+    dare.judge_fee_bps = judge_fee_bps;
+    dare.description_revealed = false;
+    dare.revealed_at = 0;
+    dare.proof_attempts = 0;
+    dare.proof_submitted_at = 0;
+    dare.dispute_window = dispute_window;
+    dare.accept_by = deadline;
+    dare.time_to_complete = time_to_complete;
+    dare.min_reputation = min_reputation;
+    dare.challenger_reputation = ctx.accounts.challenger_stats.reputation;
+    dare.version = DARE_VERSION;
 
     // Set target daree if specified (for targeted DirectDares)
     if has_target {
@@ -54,46 +197,128 @@ pub fn handler(
         dare.has_daree = false;
     }
 
-    // Transfer SOL from challenger to vault
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.challenger.to_account_info(),
-                to: ctx.accounts.vault.to_account_info(),
-            },
-        ),
+    // Token dares escrow the given mint; otherwise the dare is denominated in SOL
+    match &ctx.accounts.mint {
+        Some(mint) => {
+            validate_mint(mint, ctx.accounts.allowed_mint.is_some())?;
+            dare.mint = mint.key();
+            dare.has_mint = true;
+        }
+        None => {
+            dare.mint = Pubkey::default();
+            dare.has_mint = false;
+        }
+    }
+
+    // Transfer SOL (or tokens) from challenger to vault
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let received = escrow.deposit(
+        &ctx.accounts.challenger,
+        ctx.accounts.challenger_token_account.as_ref(),
         amount,
     )?;
 
+    // Transfer-fee mints withhold part of the deposit, so record what actually landed in escrow
+    ctx.accounts.dare.amount = received;
+
+    // SOL vaults can't be left below rent-exemption between payouts, so every share
+    // (and every forfeitable streak slice) must cover it
+    if winner_count > 1 && !ctx.accounts.dare.has_mint {
+        let rent_minimum = Rent::get()?.minimum_balance(0);
+        for &bps in &payout_bps[..winner_count as usize] {
+            let share = (received as u128) * (bps as u128) / BPS_DENOMINATOR as u128;
+            require!(share >= rent_minimum as u128, DareMeError::PayoutShareTooSmall);
+        }
+    }
+    if streak_periods > 0 && miss_policy == MissPolicy::ForfeitSlice && !ctx.accounts.dare.has_mint {
+        let rent_minimum = Rent::get()?.minimum_balance(0);
+        require!(ctx.accounts.dare.streak_slice() >= rent_minimum, DareMeError::PayoutShareTooSmall);
+    }
+
     // Update challenger stats
+    let has_mint = ctx.accounts.dare.has_mint;
     let stats = &mut ctx.accounts.challenger_stats;
     if stats.user == Pubkey::default() {
         stats.user = ctx.accounts.challenger.key();
         stats.bump = ctx.bumps.challenger_stats;
+        stats.version = USER_STATS_VERSION;
     }
     stats.dares_created += 1;
-    stats.total_spent = stats.total_spent.checked_add(amount).ok_or(DareMeError::ArithmeticOverflow)?;
+    if assigned {
+        stats.next_dare_id = stats.next_dare_id
+            .checked_add(1)
+            .ok_or(DareMeError::ArithmeticOverflow)?;
+    }
+    if !has_mint {
+        stats.total_spent = stats.total_spent.checked_add(amount).ok_or(DareMeError::ArithmeticOverflow)?;
+    }
+
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(DareCreated {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        target_daree,
+        dare_id,
+        description_hash,
+        amount: received,
+        mint: dare.mint,
+        dare_type,
+        winner_selection,
+        winner_count,
+        daree_stake,
+        streak_periods,
+        streak_period,
+        miss_policy,
+        judge,
+        judge_fee_bps,
+        dispute_window,
+        deadline,
+        time_to_complete,
+        min_reputation,
+        status: dare.status,
+        created_at: now,
+    });
 
-    msg!("Dare created: id={}, amount={}, type={:?}, target={}", dare_id, amount, dare_type, target_daree);
-    Ok(())
+    msg!(
+        "Dare created: id={}, amount={}, type={:?}, target={}, mint={}, winners={}, stake={}, streak={}x{}s, judge={}",
+        dare_id, received, dare_type, target_daree, ctx.accounts.dare.mint, winner_count, daree_stake,
+        streak_periods, streak_period, judge
+    );
+    Ok(dare_id)
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(dare_id: u64)]
 pub struct CreateDare<'info> {
     #[account(mut)]
     pub challenger: Signer<'info>,
 
+    /// Program-assigned IDs are read from challenger_stats' raw data, since the dare is created first
     #[account(
         init,
         payer = challenger,
         space = Dare::SPACE,
-        seeds = [DARE_SEED, challenger.key().as_ref(), &dare_id.to_le_bytes()],
+        seeds = [DARE_SEED, challenger.key().as_ref(), &UserStats::dare_id_seed(challenger_stats, dare_id)],
         bump,
     )]
     pub dare: Account<'info, Dare>,
 
+    /// Blocks new money from entering while the program is paused
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ DareMeError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
     /// CHECK: Vault PDA — just receives SOL, no data. Validated by seeds.
     #[account(
         mut,
@@ -108,8 +333,39 @@ pub struct CreateDare<'info> {
         space = UserStats::SPACE,
         seeds = [USER_STATS_SEED, challenger.key().as_ref()],
         bump,
+        constraint = challenger_stats.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub challenger_stats: Account<'info, UserStats>,
 
+    /// Optional: only for token dares. Omit for SOL dares.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: the mint's allowlist entry, required when the mint has a freeze authority
+    #[account(
+        constraint = mint.as_ref().map(|mint| mint.key()) == Some(allowed_mint.mint) @ DareMeError::InvalidMint,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// Optional: challenger's token account funding the escrow
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = challenger,
+        token::token_program = token_program,
+    )]
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        init,
+        payer = challenger,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
     pub system_program: Program<'info, System>,
 }
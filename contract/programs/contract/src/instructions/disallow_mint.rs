@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin removes a mint from the allowlist. Dares already escrowing it are unaffected.
+pub fn handler(ctx: Context<DisallowMint>) -> Result<()> {
+    let mint = ctx.accounts.allowed_mint.mint;
+
+    emit_cpi!(MintAllowlistUpdated {
+        admin: ctx.accounts.admin.key(),
+        mint,
+        allowed: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Mint removed from allowlist: {}", mint);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DisallowMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [ALLOWED_MINT_SEED, allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Daree escalates a rejected proof to the arbiter. The dare moves to Disputed, which freezes
+/// the escrow: no other instruction can move funds until resolve_dispute rules on it,
+/// or ARBITER_TIMEOUT passes and expire_dare lapses the dispute.
+/// Open for the dare's dispute window after the rejection (see Dare::dispute_closes_at), the same
+/// window expire_dare waits out.
+pub fn handler(ctx: Context<DisputeRejection>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    require!(ctx.accounts.config.arbiter != Pubkey::default(), DareMeError::ArbiterNotSet);
+
+    let dare = &mut ctx.accounts.dare;
+    require!(dare.status == DareStatus::Rejected, DareMeError::InvalidDareStatus);
+    // Parallel bounty submissions are judged one by one and have no single daree to side with
+    require!(!dare.takes_submissions(), DareMeError::InvalidDareType);
+    require!(
+        dare.has_daree && ctx.accounts.daree.key() == dare.daree,
+        DareMeError::UnauthorizedDaree
+    );
+    require!(now <= dare.dispute_closes_at(), DareMeError::DisputeWindowClosed);
+
+    dare.status = DareStatus::Disputed;
+    dare.disputed_at = now;
+
+    emit_cpi!(DisputeOpened {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        daree: dare.daree,
+        proof_hash: dare.proof_hash,
+        timestamp: now,
+    });
+
+    msg!("Dare {} rejection disputed by {}", dare.dare_id, dare.daree);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DisputeRejection<'info> {
+    pub daree: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
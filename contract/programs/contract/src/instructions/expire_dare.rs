@@ -1,25 +1,105 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::error::DareMeError;
+use crate::events::*;
+use crate::escrow::Escrow;
 use crate::state::*;
 
+/// Settles a dare its deadline, review window or arbiter timeout has run out on.
+/// A judge is paid their fee only when their rejection stands: it went undisputed through the
+/// dispute window here, or resolve_dispute upheld it. A lapsed dispute pays them nothing.
 pub fn handler(ctx: Context<ExpireDare>) -> Result<()> {
     // Capture values before mutable borrow
-    let dare_key = ctx.accounts.dare.key();
-    let vault_bump = ctx.accounts.dare.vault_bump;
-    let dare_amount = ctx.accounts.dare.amount;
+    let has_mint = ctx.accounts.dare.has_mint;
     let dare_id = ctx.accounts.dare.dare_id;
 
-    let dare = &mut ctx.accounts.dare;
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
 
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let dare = &mut ctx.accounts.dare;
+
+    if let Some(judge_stats) = ctx.accounts.judge_stats.as_mut() {
+        if judge_stats.user == Pubkey::default() {
+            judge_stats.user = dare.judge;
+            judge_stats.bump = ctx.bumps.judge_stats.ok_or(DareMeError::MissingJudge)?;
+            judge_stats.version = USER_STATS_VERSION;
+        }
+    }
+
     match dare.status {
         DareStatus::Created | DareStatus::Active | DareStatus::Rejected => {
-            require!(now > dare.deadline, DareMeError::DareNotExpired);
+            // Streaks are checked period by period, not just at the deadline
+            let missed = if dare.status == DareStatus::Active && dare.is_streak() {
+                dare.settle_missed_periods(now)
+            } else {
+                0
+            };
+            if missed == 0 {
+                require!(now > dare.deadline, DareMeError::DareNotExpired);
+            }
+
+            // Under ForfeitSlice a miss only costs its slice, unless the streak ended with no check-ins at all
+            let streak_over = now >= dare.deadline;
+            if missed > 0
+                && dare.miss_policy == MissPolicy::ForfeitSlice
+                && !(streak_over && dare.checkins == 0)
+            {
+                require!(
+                    ctx.accounts.recipient.key() == dare.challenger,
+                    DareMeError::UnauthorizedChallenger
+                );
+
+                let forfeited = dare.streak_slice()
+                    .checked_mul(missed as u64)
+                    .ok_or(DareMeError::ArithmeticOverflow)?
+                    .min(escrow.balance()?);
+                escrow.release(
+                    &ctx.accounts.recipient.to_account_info(),
+                    ctx.accounts.recipient_token_account.as_ref(),
+                    forfeited,
+                )?;
+
+                if !has_mint {
+                    let challenger_stats = &mut ctx.accounts.challenger_stats;
+                    challenger_stats.total_spent = challenger_stats.total_spent.saturating_sub(forfeited);
+                }
+
+                // Once the last period has closed, what the check-ins earned goes to the challenger for review
+                if streak_over {
+                    dare.status = DareStatus::ProofSubmitted;
+                    dare.proof_submitted_at = now;
+                }
+
+                emit_cpi!(StreakPeriodsMissed {
+                    dare: dare.key(),
+                    challenger: dare.challenger,
+                    daree: dare.daree,
+                    missed,
+                    total_missed: dare.missed_periods,
+                    forfeited,
+                    status: dare.status,
+                    timestamp: now,
+                });
+
+                msg!("Dare {} missed {} streak period(s). {} forfeited to challenger.", dare_id, missed, forfeited);
+                return Ok(());
+            }
+            // A rejected daree keeps the dispute window to escalate before the challenger is refunded
+            if dare.status == DareStatus::Rejected && !dare.takes_submissions() {
+                require!(now > dare.dispute_closes_at(), DareMeError::DisputeWindowActive);
+            }
 
+            let judge_ruled = dare.status == DareStatus::Rejected && dare.has_judge;
             dare.status = DareStatus::Expired;
 
             // Verify recipient is the challenger (refund)
@@ -28,43 +108,95 @@ pub fn handler(ctx: Context<ExpireDare>) -> Result<()> {
                 DareMeError::UnauthorizedChallenger
             );
 
-            // Refund SOL from vault to challenger using CPI with PDA signer
-            let vault_seeds: &[&[u8]] = &[VAULT_SEED, dare_key.as_ref(), &[vault_bump]];
-            let vault_lamports = ctx.accounts.vault.lamports();
-
-            system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    system_program::Transfer {
-                        from: ctx.accounts.vault.to_account_info(),
-                        to: ctx.accounts.recipient.to_account_info(),
-                    },
-                    &[vault_seeds],
-                ),
-                vault_lamports,
+            // Refund the challenger's share of whatever is left in escrow (the unpaid remainder
+            // for split bounties and milestone dares) from vault using CPI with PDA signer; backers claim theirs separately.
+            // A daree who never delivered forfeits their counter-stake to the challenger.
+            let forfeited = dare.stake_held;
+
+            // A judge whose rejection stood is paid for the ruling out of the reward before the refund
+            let mut judge_fee = 0;
+            if judge_ruled {
+                let judge = ctx.accounts.judge.as_ref().ok_or(DareMeError::MissingJudge)?;
+                let judge_stats = ctx.accounts.judge_stats.as_mut().ok_or(DareMeError::MissingJudge)?;
+                judge_fee = dare.judge_fee_on(escrow.balance()?.saturating_sub(forfeited))?;
+                escrow.release(
+                    &judge.to_account_info(),
+                    ctx.accounts.judge_token_account.as_ref(),
+                    judge_fee,
+                )?;
+                if !has_mint {
+                    judge_stats.judge_fees_earned = judge_stats.judge_fees_earned
+                        .checked_add(judge_fee)
+                        .ok_or(DareMeError::ArithmeticOverflow)?;
+                }
+            }
+
+            let released = escrow.releasable(dare.start_refund(escrow.balance()?)?)?;
+            escrow.release(
+                &ctx.accounts.recipient.to_account_info(),
+                ctx.accounts.recipient_token_account.as_ref(),
+                released,
             )?;
 
             // Update challenger stats (refund)
-            let challenger_stats = &mut ctx.accounts.challenger_stats;
-            challenger_stats.total_spent = challenger_stats.total_spent.saturating_sub(dare_amount);
+            if !has_mint {
+                let challenger_stats = &mut ctx.accounts.challenger_stats;
+                challenger_stats.total_spent = challenger_stats.total_spent
+                    .saturating_sub(released.saturating_sub(forfeited));
+            }
 
-            // Update daree stats if daree exists (they failed)
-            if dare.has_daree {
+            // Update daree stats if daree exists and was never paid (they failed).
+            // A milestone daree who only got partway failed too; their paid milestones still count.
+            if dare.has_daree && (dare.winners_paid == 0 || dare.is_milestone_dare()) {
                 require!(
                     ctx.accounts.daree_stats.is_some(),
                     DareMeError::MissingDareeStats
                 );
                 if let Some(daree_stats) = &mut ctx.accounts.daree_stats {
-                    daree_stats.dares_failed += 1;
+                    require_keys_eq!(daree_stats.user, dare.daree, DareMeError::MissingDareeStats);
+                    daree_stats.record_failure();
                 }
             }
 
-            msg!("Dare {} expired. {} lamports refunded to challenger.", dare_id, vault_lamports);
+            emit_cpi!(DareExpired {
+                dare: dare.key(),
+                challenger: dare.challenger,
+                daree: dare.daree,
+                accepted: dare.accepted_at > 0,
+                refunded: released,
+                forfeited_stake: forfeited,
+                judge_fee,
+                status: dare.status,
+                expired_at: now,
+            });
+
+            msg!(
+                "Dare {} expired{}. {} refunded to challenger ({} forfeited stake, {} judge fee).",
+                dare_id,
+                if dare.accepted_at > 0 { "" } else { " unaccepted" },
+                released, forfeited, judge_fee
+            );
         }
 
         DareStatus::ProofSubmitted => {
+            // CommunityVote dares are settled by tally_votes instead
+            require!(
+                dare.winner_selection == WinnerSelection::ChallengerSelect,
+                DareMeError::CommunityVoteDare
+            );
+
+            // Bounties with parallel submissions auto-approve the pending submission passed in.
+            // The review window runs from when the proof being approved was submitted.
+            let submitted_at = if dare.takes_submissions() {
+                let submission = ctx.accounts.submission.as_mut().ok_or(DareMeError::MissingSubmission)?;
+                let submitted_at = submission.submitted_at;
+                dare.award_submission(submission)?;
+                submitted_at
+            } else {
+                dare.proof_submitted_at
+            };
             require!(
-                now > dare.deadline + DISPUTE_WINDOW,
+                now > submitted_at + dare.dispute_window,
                 DareMeError::DisputeWindowActive
             );
 
@@ -74,38 +206,116 @@ pub fn handler(ctx: Context<ExpireDare>) -> Result<()> {
                 DareMeError::UnauthorizedDaree
             );
 
-            dare.status = DareStatus::Completed;
-            dare.completed_at = now;
-
-            // Release SOL from vault to daree using CPI with PDA signer
-            let vault_seeds: &[&[u8]] = &[VAULT_SEED, dare_key.as_ref(), &[vault_bump]];
-            let vault_lamports = ctx.accounts.vault.lamports();
-
-            system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    system_program::Transfer {
-                        from: ctx.accounts.vault.to_account_info(),
-                        to: ctx.accounts.recipient.to_account_info(),
-                    },
-                    &[vault_seeds],
-                ),
-                vault_lamports,
+            // Release the daree's share of escrow from vault to daree using CPI with PDA signer,
+            // minus the protocol fee on the reward (never on the daree's own stake)
+            let released = dare.next_payout(escrow.balance()?)?;
+            let reward = dare.reward_portion(released);
+            let fee = ctx.accounts.config.fee_on(reward)?;
+            escrow.release(
+                &ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.treasury_token_account.as_ref(),
+                fee,
+            )?;
+            escrow.release(
+                &ctx.accounts.recipient.to_account_info(),
+                ctx.accounts.recipient_token_account.as_ref(),
+                released - fee,
             )?;
+            let earned = reward - fee;
+            let decimals = escrow.decimals();
+            dare.record_payout(now);
 
             // Update daree stats (they completed it)
             require!(
                 ctx.accounts.daree_stats.is_some(),
                 DareMeError::MissingDareeStats
             );
+            let dealings = ctx.accounts.dealings.as_mut().ok_or(DareMeError::MissingDealings)?;
+            dealings.open(&dare.challenger, &dare.daree, ctx.bumps.dealings.ok_or(DareMeError::MissingDealings)?);
             if let Some(daree_stats) = &mut ctx.accounts.daree_stats {
-                daree_stats.dares_completed += 1;
-                daree_stats.total_earned = daree_stats.total_earned
-                    .checked_add(dare_amount)
-                    .ok_or(DareMeError::ArithmeticOverflow)?;
+                require_keys_eq!(daree_stats.user, dare.daree, DareMeError::MissingDareeStats);
+                if dare.is_milestone_dare() {
+                    daree_stats.milestones_completed += 1;
+                }
+                if dare.payout_completes_dare() {
+                    daree_stats.dares_completed += 1;
+                }
+                if !has_mint {
+                    daree_stats.total_earned = daree_stats.total_earned
+                        .checked_add(earned)
+                        .ok_or(DareMeError::ArithmeticOverflow)?;
+                }
+                daree_stats.credit_reputation(dare, earned, decimals, dare.payout_completes_dare(), dealings);
             }
 
-            msg!("Dare {} auto-approved. {} lamports released to daree.", dare_id, vault_lamports);
+            // Whoever was meant to review the proof let it run out
+            if dare.has_judge {
+                ctx.accounts.judge_stats.as_mut().ok_or(DareMeError::MissingJudge)?.record_ghosting();
+            } else {
+                ctx.accounts.challenger_stats.record_ghosting();
+            }
+
+            emit_cpi!(DareAutoApproved {
+                dare: dare.key(),
+                challenger: dare.challenger,
+                daree: dare.daree,
+                amount: released - fee,
+                fee,
+                winners_paid: dare.winners_paid,
+                status: dare.status,
+                approved_at: now,
+            });
+
+            msg!("Dare {} auto-approved. {} released to daree ({} fee).", dare_id, released - fee, fee);
+        }
+
+        DareStatus::Disputed => {
+            // An arbiter who never rules can't keep the escrow frozen. Once ARBITER_TIMEOUT has passed the
+            // dispute lapses with nobody at fault: the daree gets their own stake back and the rest is
+            // refunded to the challenger, with backers claiming theirs separately. No failure is recorded.
+            require!(now > dare.disputed_at + ARBITER_TIMEOUT, DareMeError::ArbiterStillDeciding);
+            require!(
+                ctx.accounts.recipient.key() == dare.challenger,
+                DareMeError::UnauthorizedChallenger
+            );
+
+            let returned_stake = dare.stake_held;
+            if returned_stake > 0 {
+                let daree = ctx.accounts.daree.as_ref().ok_or(DareMeError::MissingDaree)?;
+                escrow.release(
+                    &daree.to_account_info(),
+                    ctx.accounts.daree_token_account.as_ref(),
+                    returned_stake,
+                )?;
+                dare.stake_held = 0;
+            }
+
+            dare.status = DareStatus::Expired;
+            let released = escrow.releasable(dare.start_refund(escrow.balance()?)?)?;
+            escrow.release(
+                &ctx.accounts.recipient.to_account_info(),
+                ctx.accounts.recipient_token_account.as_ref(),
+                released,
+            )?;
+
+            if !has_mint {
+                let challenger_stats = &mut ctx.accounts.challenger_stats;
+                challenger_stats.total_spent = challenger_stats.total_spent.saturating_sub(released);
+            }
+
+            emit_cpi!(DisputeLapsed {
+                dare: dare.key(),
+                challenger: dare.challenger,
+                daree: dare.daree,
+                to_daree: returned_stake,
+                to_challenger: released,
+                timestamp: now,
+            });
+
+            msg!(
+                "Dare {} dispute lapsed without a ruling. {} returned to daree, {} refunded to challenger.",
+                dare_id, returned_stake, released
+            );
         }
 
         _ => {
@@ -116,6 +326,7 @@ pub fn handler(ctx: Context<ExpireDare>) -> Result<()> {
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ExpireDare<'info> {
     /// Anyone can call this (permissionless crank)
@@ -126,6 +337,7 @@ pub struct ExpireDare<'info> {
         mut,
         seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
         bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub dare: Account<'info, Dare>,
 
@@ -145,16 +357,120 @@ pub struct ExpireDare<'info> {
         mut,
         seeds = [USER_STATS_SEED, dare.challenger.as_ref()],
         bump = challenger_stats.bump,
+        constraint = challenger_stats.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub challenger_stats: Account<'info, UserStats>,
 
-    /// Optional: only needed when dare has a daree
+    /// Optional: only needed when dare has a daree (or a bounty submission is auto-approved)
+    /// Must be the daree's stats — validated in handler, since a bounty's daree is only known once a submission wins
     #[account(
         mut,
-        seeds = [USER_STATS_SEED, dare.daree.as_ref()],
-        bump,
+        constraint = daree_stats.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub daree_stats: Option<Account<'info, UserStats>>,
 
+    /// Optional: the pending submission to auto-approve for PublicBounty dares settled by the challenger
+    #[account(
+        mut,
+        seeds = [SUBMISSION_SEED, dare.key().as_ref(), recipient.key().as_ref()],
+        bump = submission.bump,
+    )]
+    pub submission: Option<Account<'info, Submission>>,
+
+    /// Optional: only needed to auto-approve; dares the challenger and daree have completed together
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Dealings::SPACE,
+        seeds = [
+            DEALINGS_SEED,
+            Dealings::lower(&dare.challenger, recipient.key),
+            Dealings::higher(&dare.challenger, recipient.key),
+        ],
+        bump,
+    )]
+    pub dealings: Option<Account<'info, Dealings>>,
+
+    /// Optional: only needed to return the daree's stake when a dispute lapses without a ruling
+    #[account(mut, address = dare.daree @ DareMeError::UnauthorizedDaree)]
+    pub daree: Option<SystemAccount<'info>>,
+
+    /// Optional: only needed to pay the judge of a dare whose rejection stood
+    #[account(mut, address = dare.judge @ DareMeError::InvalidJudge)]
+    pub judge: Option<SystemAccount<'info>>,
+
+    /// Optional: the judge's stats, alongside `judge`, or on their own when the judge let a proof auto-approve
+    /// (created if the judge has none yet)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SPACE,
+        seeds = [USER_STATS_SEED, dare.judge.as_ref()],
+        bump,
+        constraint = judge_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub judge_stats: Option<Account<'info, UserStats>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = treasury @ DareMeError::InvalidTreasury,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury receiving the fee on auto-approve — validated via has_one on config
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: recipient's token account receiving the payout
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = recipient,
+        token::token_program = token_program,
+    )]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: judge's token account receiving the judge fee on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = judge,
+        token::token_program = token_program,
+    )]
+    pub judge_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: daree's token account receiving their stake back on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = daree,
+        token::token_program = token_program,
+    )]
+    pub daree_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: treasury's token account receiving the fee on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
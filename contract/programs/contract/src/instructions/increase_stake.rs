@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::escrow::Escrow;
+use crate::events::*;
+use crate::state::*;
+
+/// Challenger sweetens a dare that's still open or in progress by moving more into escrow
+pub fn handler(ctx: Context<IncreaseStake>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let dare = &ctx.accounts.dare;
+    require!(amount > 0, DareMeError::InvalidAmount);
+    require!(
+        dare.status == DareStatus::Created || dare.status == DareStatus::Active,
+        DareMeError::InvalidDareStatus
+    );
+    require!(dare.deadline > now, DareMeError::DareExpired);
+
+    // Transfer SOL (or tokens) from challenger to vault
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let received = escrow.deposit(
+        &ctx.accounts.challenger,
+        ctx.accounts.challenger_token_account.as_ref(),
+        amount,
+    )?;
+
+    let dare = &mut ctx.accounts.dare;
+    dare.amount = dare.amount.checked_add(received).ok_or(DareMeError::ArithmeticOverflow)?;
+
+    // Update challenger stats
+    if !dare.has_mint {
+        let stats = &mut ctx.accounts.challenger_stats;
+        stats.total_spent = stats.total_spent.checked_add(amount).ok_or(DareMeError::ArithmeticOverflow)?;
+    }
+
+    emit_cpi!(StakeIncreased {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        added: received,
+        new_amount: dare.amount,
+        status: dare.status,
+        increased_at: now,
+    });
+
+    msg!("Dare {} raised by {}. Pot is now {}.", dare.dare_id, received, dare.amount);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct IncreaseStake<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        has_one = challenger,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    /// Blocks new money from entering while the program is paused
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ DareMeError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, challenger.key().as_ref()],
+        bump = challenger_stats.bump,
+        constraint = challenger_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub challenger_stats: Account<'info, UserStats>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: challenger's token account funding the escrow
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = challenger,
+        token::token_program = token_program,
+    )]
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::state::*;
+
+/// One-time setup of the program-wide config. Only the program's upgrade authority can run it,
+/// and becomes the admin, so nobody can front-run the deployment and take over fees.
+pub fn handler(ctx: Context<InitializeConfig>, treasury: Pubkey, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, DareMeError::FeeTooHigh);
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.treasury = treasury;
+    config.fee_bps = fee_bps;
+    config.bump = ctx.bumps.config;
+    config.guardian = Pubkey::default();
+    config.paused = false;
+    config.arbiter = Pubkey::default();
+    config.oracle = Pubkey::default();
+    config.min_dispute_window = DEFAULT_MIN_DISPUTE_WINDOW;
+    config.max_dispute_window = DEFAULT_MAX_DISPUTE_WINDOW;
+
+    msg!("Config initialized: admin={}, treasury={}, fee_bps={}", config.admin, treasury, fee_bps);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Config::SPACE,
+        seeds = [CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Contract>,
+
+    /// The program's ProgramData account, whose upgrade authority must be the admin
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
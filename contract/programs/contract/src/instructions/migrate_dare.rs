@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::migration::*;
+use crate::state::*;
+
+/// Anyone moves a dare onto the current layout, paying any extra rent.
+/// A version 0 dare is read from its old layout and given defaults (see Dare::from_legacy). A proof
+/// it has under review was only kept on the dare back then, so it is recorded as the first
+/// ProofAttempt, and on a challenger-select bounty also as the submitter's Submission.
+pub fn handler(ctx: Context<MigrateDare>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let dare_info = ctx.accounts.dare.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let (from_version, mut dare) = {
+        let data = dare_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *Dare::DISCRIMINATOR,
+            DareMeError::UnknownAccountLayout
+        );
+        match data.len() {
+            LEGACY_DARE_SPACE => {
+                let legacy = LegacyDare::deserialize(&mut &data[8..])
+                    .map_err(|_| DareMeError::UnknownAccountLayout)?;
+                (0, Dare::from_legacy(legacy, now))
+            }
+            Dare::SPACE => {
+                let mut dare = Dare::try_deserialize(&mut &data[..])?;
+                require!(!dare.is_current(), DareMeError::AlreadyMigrated);
+                // Fields added since come out of `reserved`, which is still zeroed
+                let from_version = dare.version;
+                dare.version = DARE_VERSION;
+                (from_version, dare)
+            }
+            _ => return err!(DareMeError::UnknownAccountLayout),
+        }
+    };
+
+    if from_version == 0 {
+        let dare_key = dare_info.key();
+
+        if dare.status == DareStatus::ProofSubmitted && dare.has_proof {
+            let proof_attempt = ctx
+                .accounts
+                .proof_attempt
+                .as_ref()
+                .ok_or(DareMeError::MissingProofAttempt)?
+                .to_account_info();
+            let index = 0u32.to_le_bytes();
+            let (expected, bump) = Pubkey::find_program_address(
+                &[PROOF_ATTEMPT_SEED, dare_key.as_ref(), &index],
+                &crate::ID,
+            );
+            require_keys_eq!(proof_attempt.key(), expected, DareMeError::InvalidProofAttempt);
+            create_pda_account(
+                &proof_attempt,
+                &payer,
+                &system_program,
+                ProofAttempt::SPACE,
+                &[PROOF_ATTEMPT_SEED, dare_key.as_ref(), &index, &[bump]],
+            )?;
+            write_account(
+                &proof_attempt,
+                &ProofAttempt {
+                    dare: dare_key,
+                    submitter: dare.daree,
+                    index: 0,
+                    proof_hash: dare.proof_hash,
+                    submitted_at: dare.proof_submitted_at,
+                    rejected_at: 0,
+                    has_rejection_reason: false,
+                    rejection_reason_hash: [0u8; 32],
+                    bump,
+                },
+            )?;
+            dare.proof_attempts = 1;
+
+            if dare.takes_submissions() {
+                let submission = ctx
+                    .accounts
+                    .submission
+                    .as_ref()
+                    .ok_or(DareMeError::MissingSubmission)?
+                    .to_account_info();
+                let (expected, bump) = Pubkey::find_program_address(
+                    &[SUBMISSION_SEED, dare_key.as_ref(), dare.daree.as_ref()],
+                    &crate::ID,
+                );
+                require_keys_eq!(submission.key(), expected, DareMeError::InvalidSubmission);
+                create_pda_account(
+                    &submission,
+                    &payer,
+                    &system_program,
+                    Submission::SPACE,
+                    &[SUBMISSION_SEED, dare_key.as_ref(), dare.daree.as_ref(), &[bump]],
+                )?;
+                write_account(
+                    &submission,
+                    &Submission {
+                        dare: dare_key,
+                        submitter: dare.daree,
+                        proof_hash: dare.proof_hash,
+                        submitted_at: dare.proof_submitted_at,
+                        status: SubmissionStatus::Pending,
+                        bump,
+                        attempt: 0,
+                    },
+                )?;
+                dare.pending_submissions = 1;
+            }
+        }
+
+        // Challenger-select bounties now keep submitters in Submission PDAs and only name a daree
+        // once one is approved
+        if dare.takes_submissions() && dare.status != DareStatus::Completed {
+            dare.daree = Pubkey::default();
+            dare.has_daree = false;
+            dare.has_proof = false;
+        }
+    }
+
+    grow_account(&dare_info, &payer, &system_program, Dare::SPACE)?;
+    write_account(&dare_info, &dare)?;
+
+    emit_cpi!(AccountMigrated {
+        account: dare_info.key(),
+        payer: payer.key(),
+        from_version,
+        to_version: DARE_VERSION,
+        timestamp: now,
+    });
+
+    msg!("Dare {} migrated from version {} to {}", dare.dare_id, from_version, DARE_VERSION);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateDare<'info> {
+    /// Anyone can migrate; pays for the larger account and any PDAs created
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Read by hand since older layouts don't deserialize as Dare; owner and discriminator checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub dare: UncheckedAccount<'info>,
+
+    /// Optional: the dare's first ProofAttempt PDA, created for a version 0 dare with a proof under review
+    /// CHECK: Address checked in the handler
+    #[account(mut)]
+    pub proof_attempt: Option<UncheckedAccount<'info>>,
+
+    /// Optional: the Submission PDA of a version 0 challenger-select bounty's submitter, created alongside it
+    /// CHECK: Address checked in the handler
+    #[account(mut)]
+    pub submission: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
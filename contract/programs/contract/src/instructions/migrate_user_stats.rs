@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::migration::*;
+use crate::state::*;
+
+/// Anyone moves a user's stats onto the current layout, paying any extra rent.
+/// Version 0 stats are read from their old layout (see UserStats::from_legacy).
+pub fn handler(ctx: Context<MigrateUserStats>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stats_info = ctx.accounts.user_stats.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+
+    let (from_version, stats) = {
+        let data = stats_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *UserStats::DISCRIMINATOR,
+            DareMeError::UnknownAccountLayout
+        );
+        match data.len() {
+            LEGACY_USER_STATS_SPACE => {
+                let legacy = LegacyUserStats::deserialize(&mut &data[8..])
+                    .map_err(|_| DareMeError::UnknownAccountLayout)?;
+                (0, UserStats::from_legacy(legacy))
+            }
+            UserStats::SPACE => {
+                let mut stats = UserStats::try_deserialize(&mut &data[..])?;
+                require!(!stats.is_current(), DareMeError::AlreadyMigrated);
+                // Fields added since come out of `reserved`, which is still zeroed
+                let from_version = stats.version;
+                stats.version = USER_STATS_VERSION;
+                (from_version, stats)
+            }
+            _ => return err!(DareMeError::UnknownAccountLayout),
+        }
+    };
+
+    grow_account(
+        &stats_info,
+        &payer,
+        &ctx.accounts.system_program.to_account_info(),
+        UserStats::SPACE,
+    )?;
+    write_account(&stats_info, &stats)?;
+
+    emit_cpi!(AccountMigrated {
+        account: stats_info.key(),
+        payer: payer.key(),
+        from_version,
+        to_version: USER_STATS_VERSION,
+        timestamp: now,
+    });
+
+    msg!("Stats for {} migrated from version {} to {}", stats.user, from_version, USER_STATS_VERSION);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateUserStats<'info> {
+    /// Anyone can migrate; pays for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Read by hand since older layouts don't deserialize as UserStats; owner and discriminator checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub user_stats: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
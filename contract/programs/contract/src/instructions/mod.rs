@@ -1,3 +1,5 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod create_dare;
 pub mod accept_dare;
 pub mod submit_proof;
@@ -6,6 +8,30 @@ pub mod reject_dare;
 pub mod cancel_dare;
 pub mod expire_dare;
 pub mod refuse_dare;
+pub mod cast_vote;
+pub mod tally_votes;
+pub mod back_dare;
+pub mod claim_refund;
+pub mod initialize_config;
+pub mod update_fee;
+pub mod update_treasury;
+pub mod set_paused;
+pub mod update_guardian;
+pub mod update_arbiter;
+pub mod dispute_rejection;
+pub mod resolve_dispute;
+pub mod close_dare;
+pub mod propose_deadline_extension;
+pub mod accept_deadline_extension;
+pub mod increase_stake;
+pub mod update_oracle;
+pub mod settle_with_attestation;
+pub mod reveal_description;
+pub mod update_dispute_window_bounds;
+pub mod migrate_dare;
+pub mod migrate_user_stats;
+pub mod allow_mint;
+pub mod disallow_mint;
 
 pub use create_dare::*;
 pub use accept_dare::*;
@@ -15,3 +41,27 @@ pub use reject_dare::*;
 pub use cancel_dare::*;
 pub use expire_dare::*;
 pub use refuse_dare::*;
+pub use cast_vote::*;
+pub use tally_votes::*;
+pub use back_dare::*;
+pub use claim_refund::*;
+pub use initialize_config::*;
+pub use update_fee::*;
+pub use update_treasury::*;
+pub use set_paused::*;
+pub use update_guardian::*;
+pub use update_arbiter::*;
+pub use dispute_rejection::*;
+pub use resolve_dispute::*;
+pub use close_dare::*;
+pub use propose_deadline_extension::*;
+pub use accept_deadline_extension::*;
+pub use increase_stake::*;
+pub use update_oracle::*;
+pub use settle_with_attestation::*;
+pub use reveal_description::*;
+pub use update_dispute_window_bounds::*;
+pub use migrate_dare::*;
+pub use migrate_user_stats::*;
+pub use allow_mint::*;
+pub use disallow_mint::*;
\ No newline at end of file
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Challenger or daree proposes moving the deadline of an accepted dare later.
+/// Takes effect only once the other side accepts; a new proposal replaces any pending one.
+pub fn handler(ctx: Context<ProposeDeadlineExtension>, new_deadline: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let proposer = ctx.accounts.proposer.key();
+
+    let dare = &mut ctx.accounts.dare;
+    require!(
+        dare.status == DareStatus::Active || dare.status == DareStatus::Rejected,
+        DareMeError::InvalidDareStatus
+    );
+    // Bounties with parallel submissions have no single daree to agree with
+    require!(!dare.takes_submissions(), DareMeError::InvalidDareType);
+    // Streak periods are counted back from the deadline, so moving it would reshuffle them
+    require!(!dare.is_streak(), DareMeError::NotSupportedForStreak);
+    require!(dare.is_party(&proposer), DareMeError::NotAParty);
+    require!(dare.deadline > now, DareMeError::DareExpired);
+    require!(new_deadline > dare.deadline, DareMeError::DeadlineNotExtended);
+    require!(new_deadline <= dare.max_deadline(), DareMeError::DeadlineTooFar);
+
+    dare.proposed_deadline = new_deadline;
+    dare.proposed_by = proposer;
+
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(DeadlineExtensionProposed {
+        dare: dare.key(),
+        proposer,
+        current_deadline: dare.deadline,
+        proposed_deadline: new_deadline,
+        proposed_at: now,
+    });
+
+    msg!("Dare {} deadline extension to {} proposed by {}", dare.dare_id, new_deadline, proposer);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeDeadlineExtension<'info> {
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+}
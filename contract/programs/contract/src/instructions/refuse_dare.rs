@@ -1,25 +1,32 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::error::DareMeError;
+use crate::events::*;
+use crate::escrow::Escrow;
 use crate::state::*;
 
 /// Targeted daree refuses a DirectDare, triggering a refund to challenger.
-/// Only works on dares where has_daree=true (targeted) and status=Created.
+/// Only works on dares where has_daree=true (targeted or accepted).
+/// Refusing after acceptance (Active/Rejected) counts as a failure and forfeits the daree's stake.
 pub fn handler(ctx: Context<RefuseDare>) -> Result<()> {
     // Capture values before mutable borrow
-    let dare_key = ctx.accounts.dare.key();
-    let vault_bump = ctx.accounts.dare.vault_bump;
-    let dare_amount = ctx.accounts.dare.amount;
+    let has_mint = ctx.accounts.dare.has_mint;
     let dare_id = ctx.accounts.dare.dare_id;
 
     let dare = &mut ctx.accounts.dare;
 
     // Must be a DirectDare (PublicBounty doesn't target anyone)
     require!(dare.dare_type == DareType::DirectDare, DareMeError::InvalidDareType);
-    // Must still be in Created status (not yet accepted/active)
-    require!(dare.status == DareStatus::Created, DareMeError::InvalidDareStatus);
+    // Before proof is under review: not yet accepted, accepted, or proof rejected
+    require!(
+        dare.status == DareStatus::Created
+            || dare.status == DareStatus::Active
+            || dare.status == DareStatus::Rejected,
+        DareMeError::InvalidDareStatus
+    );
+    let accepted = dare.status != DareStatus::Created;
     // Must have a target daree set
     require!(dare.has_daree, DareMeError::NotTargetedDare);
     // Signer must be the targeted daree
@@ -30,30 +37,54 @@ pub fn handler(ctx: Context<RefuseDare>) -> Result<()> {
 
     dare.status = DareStatus::Refused;
 
-    // Refund SOL from vault to challenger
-    let vault_seeds: &[&[u8]] = &[VAULT_SEED, dare_key.as_ref(), &[vault_bump]];
-    let vault_lamports = ctx.accounts.vault.lamports();
-
-    system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.challenger.to_account_info(),
-            },
-            &[vault_seeds],
-        ),
-        vault_lamports,
+    // Refund the challenger's share of escrow from vault (backers claim theirs separately)
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let forfeited = ctx.accounts.dare.stake_held;
+    let refunded = escrow.releasable(ctx.accounts.dare.start_refund(escrow.balance()?)?)?;
+    escrow.release(
+        &ctx.accounts.challenger.to_account_info(),
+        ctx.accounts.challenger_token_account.as_ref(),
+        refunded,
     )?;
 
     // Update challenger stats (refund the spent amount)
-    let stats = &mut ctx.accounts.challenger_stats;
-    stats.total_spent = stats.total_spent.saturating_sub(dare_amount);
+    if !has_mint {
+        let stats = &mut ctx.accounts.challenger_stats;
+        stats.total_spent = stats.total_spent.saturating_sub(refunded.saturating_sub(forfeited));
+    }
+
+    // Giving up after accepting counts against the daree
+    if accepted {
+        let daree_stats = ctx.accounts.daree_stats.as_mut().ok_or(DareMeError::MissingDareeStats)?;
+        daree_stats.record_failure();
+    }
+
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(DareRefused {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        daree: dare.daree,
+        refunded,
+        forfeited_stake: forfeited,
+        status: dare.status,
+        refused_at: Clock::get()?.unix_timestamp,
+    });
 
-    msg!("Dare {} refused by {}. {} lamports refunded to challenger.", dare_id, ctx.accounts.daree.key(), vault_lamports);
+    msg!(
+        "Dare {} refused by {}. {} refunded to challenger ({} forfeited stake).",
+        dare_id, ctx.accounts.daree.key(), refunded, forfeited
+    );
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RefuseDare<'info> {
     /// The targeted daree who is refusing the dare
@@ -64,6 +95,7 @@ pub struct RefuseDare<'info> {
         mut,
         seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
         bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub dare: Account<'info, Dare>,
 
@@ -86,8 +118,40 @@ pub struct RefuseDare<'info> {
         mut,
         seeds = [USER_STATS_SEED, dare.challenger.as_ref()],
         bump = challenger_stats.bump,
+        constraint = challenger_stats.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub challenger_stats: Account<'info, UserStats>,
 
+    /// Optional: only needed when refusing after acceptance
+    #[account(
+        mut,
+        constraint = daree_stats.user == daree.key() @ DareMeError::MissingDareeStats,
+        constraint = daree_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub daree_stats: Option<Account<'info, UserStats>>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: challenger's token account receiving the payout
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = challenger,
+        token::token_program = token_program,
+    )]
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
@@ -2,30 +2,137 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::error::DareMeError;
+use crate::events::*;
 use crate::state::*;
 
-pub fn handler(ctx: Context<RejectDare>) -> Result<()> {
+/// Rejects the proof under review and stamps its ProofAttempt with the rejection time and an
+/// optional reason hash (all zeros for none), so the evidence survives a re-submission.
+pub fn handler(ctx: Context<RejectDare>, reason_hash: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // Update judge stats
+    if ctx.accounts.dare.has_judge {
+        let judge_stats = &mut ctx.accounts.authority_stats;
+        if judge_stats.user == Pubkey::default() {
+            judge_stats.user = ctx.accounts.authority.key();
+            judge_stats.bump = ctx.bumps.authority_stats;
+            judge_stats.version = USER_STATS_VERSION;
+        }
+        judge_stats.dares_judged += 1;
+    }
+
     let dare = &mut ctx.accounts.dare;
 
     require!(dare.status == DareStatus::ProofSubmitted, DareMeError::InvalidDareStatus);
+    require!(
+        dare.winner_selection == WinnerSelection::ChallengerSelect,
+        DareMeError::CommunityVoteDare
+    );
+
+    // Bounties with parallel submissions reject one submission at a time
+    if dare.takes_submissions() {
+        let submission = ctx.accounts.submission.as_mut().ok_or(DareMeError::MissingSubmission)?;
+        require!(submission.status == SubmissionStatus::Pending, DareMeError::InvalidSubmission);
+        require!(submission.submitted_at >= dare.created_at, DareMeError::InvalidSubmission);
+        require!(ctx.accounts.proof_attempt.index == submission.attempt, DareMeError::InvalidProofAttempt);
+        submission.status = SubmissionStatus::Rejected;
+        ctx.accounts.proof_attempt.reject(now, reason_hash);
+
+        dare.pending_submissions = dare.pending_submissions.saturating_sub(1);
+        if dare.pending_submissions == 0 {
+            // Split bounties that already paid a winner stay open for the remaining prizes
+            dare.status = if dare.winners_paid > 0 { DareStatus::Active } else { DareStatus::Rejected };
+            dare.rejected_at = now;
+        }
+
+        let (submitter, proof_hash) = (submission.submitter, submission.proof_hash);
+        let attempt = submission.attempt;
+        let dare = &ctx.accounts.dare;
+        emit_cpi!(ProofRejected {
+            dare: dare.key(),
+            challenger: dare.challenger,
+            judge: dare.judge,
+            submitter,
+            proof_hash,
+            attempt,
+            reason_hash,
+            status: dare.status,
+            rejected_at: now,
+        });
+
+        msg!("Dare {} submission by {} rejected. Submitter can re-submit.", dare.dare_id, submitter);
+        return Ok(());
+    }
 
+    // The rejected proof_hash is kept so the daree can take it to the arbiter
+    let attempt = dare.proof_attempts.saturating_sub(1);
+    require!(ctx.accounts.proof_attempt.index == attempt, DareMeError::InvalidProofAttempt);
     dare.status = DareStatus::Rejected;
-    dare.proof_hash = [0u8; 32];
     dare.has_proof = false;
+    dare.rejected_at = now;
+    ctx.accounts.proof_attempt.reject(now, reason_hash);
+
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(ProofRejected {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        judge: dare.judge,
+        submitter: dare.daree,
+        proof_hash: dare.proof_hash,
+        attempt,
+        reason_hash,
+        status: dare.status,
+        rejected_at: now,
+    });
 
     msg!("Dare {} proof rejected. Daree can re-submit.", dare.dare_id);
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RejectDare<'info> {
-    pub challenger: Signer<'info>,
+    /// The challenger, or the judge when the dare has one
+    #[account(
+        mut,
+        constraint = dare.can_judge(&authority.key()) @ DareMeError::UnauthorizedJudge,
+    )]
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
         seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
         bump = dare.bump,
-        has_one = challenger,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub dare: Account<'info, Dare>,
+
+    /// Only updated when the authority is a judge
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserStats::SPACE,
+        seeds = [USER_STATS_SEED, authority.key().as_ref()],
+        bump,
+        constraint = authority_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub authority_stats: Account<'info, UserStats>,
+
+    /// Optional: only needed for PublicBounty dares settled by the challenger
+    #[account(
+        mut,
+        seeds = [SUBMISSION_SEED, dare.key().as_ref(), submission.submitter.as_ref()],
+        bump = submission.bump,
+    )]
+    pub submission: Option<Account<'info, Submission>>,
+
+    /// The attempt being rejected: the latest one, or the submission's own on bounties
+    #[account(
+        mut,
+        seeds = [PROOF_ATTEMPT_SEED, dare.key().as_ref(), &proof_attempt.index.to_le_bytes()],
+        bump = proof_attempt.bump,
+    )]
+    pub proof_attempt: Account<'info, ProofAttempt>,
+
+    pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::escrow::Escrow;
+use crate::events::*;
+use crate::state::*;
+
+/// Arbiter rules on a disputed rejection.
+/// PayDaree settles like an approval, RefundChallenger like a failed dare (stake forfeited),
+/// and Split gives the daree `daree_bps` of the reward plus their stake back and refunds the rest.
+/// The protocol fee applies only to what the daree is awarded.
+/// A judge is paid their fee only when the ruling upholds their rejection in full (RefundChallenger),
+/// out of the reward before the refund, the same as when an undisputed rejection expires.
+pub fn handler(ctx: Context<ResolveDispute>, ruling: DisputeRuling) -> Result<()> {
+    // Capture values before mutable borrow
+    let has_mint = ctx.accounts.dare.has_mint;
+    let dare_id = ctx.accounts.dare.dare_id;
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    require!(ctx.accounts.dare.status == DareStatus::Disputed, DareMeError::InvalidDareStatus);
+
+    let daree_bps = match ruling {
+        DisputeRuling::PayDaree => BPS_DENOMINATOR,
+        DisputeRuling::RefundChallenger => 0,
+        DisputeRuling::Split { daree_bps } => {
+            require!(daree_bps as u64 <= BPS_DENOMINATOR, DareMeError::InvalidRuling);
+            daree_bps as u64
+        }
+    };
+
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let balance = escrow.balance()?;
+
+    // Daree's side: their share of the reward, plus their own stake unless they lost outright
+    let mut to_daree = 0;
+    let mut fee = 0;
+    if daree_bps > 0 {
+        let reward_pool = balance.saturating_sub(ctx.accounts.dare.stake_held);
+        let reward = if daree_bps == BPS_DENOMINATOR {
+            reward_pool
+        } else {
+            let share = (reward_pool as u128)
+                .checked_mul(daree_bps as u128)
+                .ok_or(DareMeError::ArithmeticOverflow)?
+                / BPS_DENOMINATOR as u128;
+            u64::try_from(share).map_err(|_| DareMeError::ArithmeticOverflow)?
+        };
+        fee = ctx.accounts.config.fee_on(reward)?;
+        let payout = reward
+            .checked_add(balance - reward_pool)
+            .ok_or(DareMeError::ArithmeticOverflow)?;
+
+        escrow.release(
+            &ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.treasury_token_account.as_ref(),
+            fee,
+        )?;
+        escrow.release(
+            &ctx.accounts.daree.to_account_info(),
+            ctx.accounts.daree_token_account.as_ref(),
+            payout - fee,
+        )?;
+        to_daree = payout - fee;
+        let decimals = escrow.decimals();
+
+        let dealings = &mut ctx.accounts.dealings;
+        dealings.open(&ctx.accounts.dare.challenger, &ctx.accounts.dare.daree, ctx.bumps.dealings);
+        let daree_stats = &mut ctx.accounts.daree_stats;
+        if daree_bps == BPS_DENOMINATOR {
+            daree_stats.dares_completed += 1;
+        }
+        if !has_mint {
+            daree_stats.total_earned = daree_stats.total_earned
+                .checked_add(reward - fee)
+                .ok_or(DareMeError::ArithmeticOverflow)?;
+        }
+        // A split is paid but doesn't count as a completion
+        daree_stats.credit_reputation(&ctx.accounts.dare, reward - fee, decimals, daree_bps == BPS_DENOMINATOR, dealings);
+
+        // The ruling settles the whole escrow, including any milestones not yet reached
+        ctx.accounts.dare.complete(now);
+    }
+
+    // Challenger's side: whatever is left is refunded (backers claim theirs separately)
+    let mut to_challenger = 0;
+    let mut judge_fee = 0;
+    let mut remaining = balance - to_daree - fee;
+    if remaining > 0 {
+        let dare = &mut ctx.accounts.dare;
+        let forfeited = dare.stake_held;
+        if daree_bps == 0 {
+            dare.status = DareStatus::Failed;
+            ctx.accounts.daree_stats.record_failure();
+
+            if dare.has_judge {
+                let judge = ctx.accounts.judge.as_ref().ok_or(DareMeError::MissingJudge)?;
+                let judge_stats = ctx.accounts.judge_stats.as_mut().ok_or(DareMeError::MissingJudge)?;
+                judge_fee = dare.judge_fee_on(remaining.saturating_sub(forfeited))?;
+                escrow.release(
+                    &judge.to_account_info(),
+                    ctx.accounts.judge_token_account.as_ref(),
+                    judge_fee,
+                )?;
+                if !has_mint {
+                    judge_stats.judge_fees_earned = judge_stats.judge_fees_earned
+                        .checked_add(judge_fee)
+                        .ok_or(DareMeError::ArithmeticOverflow)?;
+                }
+                remaining -= judge_fee;
+            }
+        }
+        to_challenger = escrow.releasable(dare.start_refund(remaining)?)?;
+        escrow.release(
+            &ctx.accounts.challenger.to_account_info(),
+            ctx.accounts.challenger_token_account.as_ref(),
+            to_challenger,
+        )?;
+
+        if !has_mint {
+            let challenger_stats = &mut ctx.accounts.challenger_stats;
+            challenger_stats.total_spent = challenger_stats.total_spent
+                .saturating_sub(to_challenger.saturating_sub(forfeited));
+        }
+    }
+
+    emit_cpi!(DisputeResolved {
+        dare: ctx.accounts.dare.key(),
+        arbiter: ctx.accounts.arbiter.key(),
+        ruling,
+        to_daree,
+        to_challenger,
+        fee,
+        judge_fee,
+        timestamp: now,
+    });
+
+    msg!(
+        "Dare {} dispute resolved ({:?}): {} to daree, {} to challenger, {} fee, {} judge fee.",
+        dare_id, ruling, to_daree, to_challenger, fee, judge_fee
+    );
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    /// Pays for `dealings` the first time the pair settles
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = arbiter @ DareMeError::UnauthorizedArbiter,
+        has_one = treasury @ DareMeError::InvalidTreasury,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury receiving the fee — validated via has_one on config
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: The challenger who gets any refund
+    #[account(
+        mut,
+        constraint = challenger.key() == dare.challenger @ DareMeError::UnauthorizedChallenger,
+    )]
+    pub challenger: SystemAccount<'info>,
+
+    /// CHECK: The daree who gets any payout
+    #[account(
+        mut,
+        constraint = daree.key() == dare.daree @ DareMeError::UnauthorizedDaree,
+    )]
+    pub daree: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, dare.challenger.as_ref()],
+        bump = challenger_stats.bump,
+        constraint = challenger_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub challenger_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, dare.daree.as_ref()],
+        bump = daree_stats.bump,
+        constraint = daree_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub daree_stats: Account<'info, UserStats>,
+
+    /// Dares the challenger and daree have completed together, which discounts the daree's reputation credit
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        space = Dealings::SPACE,
+        seeds = [
+            DEALINGS_SEED,
+            Dealings::lower(&dare.challenger, &dare.daree),
+            Dealings::higher(&dare.challenger, &dare.daree),
+        ],
+        bump,
+    )]
+    pub dealings: Account<'info, Dealings>,
+
+    /// Optional: only needed to pay the judge of a dare whose rejection the ruling upholds
+    #[account(mut, address = dare.judge @ DareMeError::InvalidJudge)]
+    pub judge: Option<SystemAccount<'info>>,
+
+    /// Optional: the judge's stats, alongside `judge`
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, dare.judge.as_ref()],
+        bump = judge_stats.bump,
+        constraint = judge_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub judge_stats: Option<Account<'info, UserStats>>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: challenger's token account receiving a refund
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = challenger,
+        token::token_program = token_program,
+    )]
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: daree's token account receiving a payout
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = daree,
+        token::token_program = token_program,
+    )]
+    pub daree_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: treasury's token account receiving the fee on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: judge's token account receiving the judge fee on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = judge,
+        token::token_program = token_program,
+    )]
+    pub judge_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Anyone publishes the dare's plaintext description. It is SHA-256 hashed on-chain and must match
+/// the description_hash committed at creation, so the text can't be swapped after the fact.
+/// Pass the exact bytes that were hashed: the trimmed UTF-8 text, at most MAX_DESCRIPTION_LEN bytes.
+pub fn handler(ctx: Context<RevealDescription>, description: String) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let dare = &mut ctx.accounts.dare;
+    require!(!dare.description_revealed, DareMeError::DescriptionAlreadyRevealed);
+    require!(description.len() <= MAX_DESCRIPTION_LEN, DareMeError::DescriptionTooLong);
+    require!(
+        hash(description.as_bytes()).to_bytes() == dare.description_hash,
+        DareMeError::DescriptionMismatch
+    );
+
+    dare.description_revealed = true;
+    dare.revealed_at = now;
+
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(DescriptionRevealed {
+        dare: dare.key(),
+        revealed_by: ctx.accounts.revealer.key(),
+        description_hash: dare.description_hash,
+        description,
+        revealed_at: now,
+    });
+
+    msg!("Dare {} description revealed", dare.dare_id);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealDescription<'info> {
+    /// Anyone who knows the plaintext can reveal it
+    pub revealer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin or guardian flips the global pause.
+/// While paused no new money can enter (create, accept, submit, back, increase_stake); exits keep working.
+pub fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let config = &mut ctx.accounts.config;
+    require!(config.can_pause(&authority), DareMeError::UnauthorizedGuardian);
+
+    config.paused = paused;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    if paused {
+        emit_cpi!(ProgramPaused { authority, timestamp });
+    } else {
+        emit_cpi!(ProgramUnpaused { authority, timestamp });
+    }
+
+    msg!("Program {} by {}", if paused { "paused" } else { "unpaused" }, authority);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
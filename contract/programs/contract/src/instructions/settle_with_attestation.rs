@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_sdk_ids::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+
+use crate::attestation::{attestation_message, verify_attestation};
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::escrow::Escrow;
+use crate::events::*;
+use crate::state::*;
+
+/// Settles a proof with the oracle's verdict instead of the challenger's signature.
+/// The transaction must carry an Ed25519 program instruction, right before this one, holding the
+/// oracle's signature over the 78-byte `attestation_message`: dare key (32), created_at (i64 LE),
+/// proof hash (32), proof attempt index (u32 LE), winners paid so far (u8), then 1 to approve or
+/// 0 to reject. Approving pays out like approve_dare, rejecting works like reject_dare.
+/// Dares with a judge are left to the judge.
+pub fn handler(ctx: Context<SettleWithAttestation>, approve: bool) -> Result<()> {
+    // Capture values before mutable borrow
+    let has_mint = ctx.accounts.dare.has_mint;
+    let dare_id = ctx.accounts.dare.dare_id;
+    let oracle = ctx.accounts.config.oracle;
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let dare = &ctx.accounts.dare;
+    require!(oracle != Pubkey::default(), DareMeError::OracleNotSet);
+    require!(dare.status == DareStatus::ProofSubmitted, DareMeError::InvalidDareStatus);
+    require!(
+        dare.winner_selection == WinnerSelection::ChallengerSelect,
+        DareMeError::CommunityVoteDare
+    );
+    require!(!dare.has_judge, DareMeError::UnauthorizedJudge);
+
+    // The attested proof: the submission being settled on a bounty, otherwise the dare's own proof
+    let (proof_hash, attempt) = if dare.takes_submissions() {
+        let submission = ctx.accounts.submission.as_ref().ok_or(DareMeError::MissingSubmission)?;
+        (submission.proof_hash, submission.attempt)
+    } else {
+        require!(
+            dare.has_daree && ctx.accounts.daree.key() == dare.daree,
+            DareMeError::UnauthorizedDaree
+        );
+        (dare.proof_hash, dare.proof_attempts.saturating_sub(1))
+    };
+    verify_attestation(
+        &ctx.accounts.instructions,
+        &oracle,
+        &attestation_message(&dare.key(), dare.created_at, &proof_hash, attempt, dare.winners_paid, approve),
+    )?;
+
+    if approve {
+        let dare = &mut ctx.accounts.dare;
+        if dare.takes_submissions() {
+            let submission = ctx.accounts.submission.as_mut().ok_or(DareMeError::MissingSubmission)?;
+            dare.award_submission(submission)?;
+        }
+
+        // Release the daree's share of escrow, minus the protocol fee on the reward
+        let escrow = Escrow::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.vault,
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        let released = ctx.accounts.dare.next_payout(escrow.balance()?)?;
+        let reward = ctx.accounts.dare.reward_portion(released);
+        let fee = ctx.accounts.config.fee_on(reward)?;
+        escrow.release(
+            &ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.treasury_token_account.as_ref(),
+            fee,
+        )?;
+        escrow.release(
+            &ctx.accounts.daree.to_account_info(),
+            ctx.accounts.daree_token_account.as_ref(),
+            released - fee,
+        )?;
+        let earned = reward - fee;
+        let decimals = escrow.decimals();
+
+        let dare = &mut ctx.accounts.dare;
+        dare.record_payout(now);
+
+        // Update daree stats
+        let dealings = ctx.accounts.dealings.as_mut().ok_or(DareMeError::MissingDealings)?;
+        dealings.open(&dare.challenger, &dare.daree, ctx.bumps.dealings.ok_or(DareMeError::MissingDealings)?);
+        let daree_stats = &mut ctx.accounts.daree_stats;
+        if dare.is_milestone_dare() {
+            daree_stats.milestones_completed += 1;
+        }
+        if dare.payout_completes_dare() {
+            daree_stats.dares_completed += 1;
+        }
+        if !has_mint {
+            daree_stats.total_earned = daree_stats.total_earned
+                .checked_add(earned)
+                .ok_or(DareMeError::ArithmeticOverflow)?;
+        }
+        daree_stats.credit_reputation(dare, earned, decimals, dare.payout_completes_dare(), dealings);
+
+        emit_cpi!(DareApproved {
+            dare: dare.key(),
+            challenger: dare.challenger,
+            judge: dare.judge,
+            daree: dare.daree,
+            amount: released - fee,
+            fee,
+            judge_fee: 0,
+            winners_paid: dare.winners_paid,
+            status: dare.status,
+            approved_at: now,
+        });
+
+        msg!("Dare {} approved by oracle. {} released ({} fee).", dare_id, released - fee, fee);
+    } else {
+        let dare = &mut ctx.accounts.dare;
+        let proof_attempt = ctx.accounts.proof_attempt.as_mut().ok_or(DareMeError::MissingProofAttempt)?;
+        let attempt = if dare.takes_submissions() {
+            let submission = ctx.accounts.submission.as_mut().ok_or(DareMeError::MissingSubmission)?;
+            require!(submission.status == SubmissionStatus::Pending, DareMeError::InvalidSubmission);
+            require!(submission.submitted_at >= dare.created_at, DareMeError::InvalidSubmission);
+            submission.status = SubmissionStatus::Rejected;
+
+            dare.pending_submissions = dare.pending_submissions.saturating_sub(1);
+            if dare.pending_submissions == 0 {
+                dare.status = if dare.winners_paid > 0 { DareStatus::Active } else { DareStatus::Rejected };
+                dare.rejected_at = now;
+            }
+            submission.attempt
+        } else {
+            // The rejected proof_hash is kept so the daree can take it to the arbiter
+            dare.status = DareStatus::Rejected;
+            dare.has_proof = false;
+            dare.rejected_at = now;
+            dare.proof_attempts.saturating_sub(1)
+        };
+        require!(proof_attempt.index == attempt, DareMeError::InvalidProofAttempt);
+        proof_attempt.reject(now, [0u8; 32]);
+
+        emit_cpi!(ProofRejected {
+            dare: dare.key(),
+            challenger: dare.challenger,
+            judge: dare.judge,
+            submitter: ctx.accounts.daree.key(),
+            proof_hash,
+            attempt,
+            reason_hash: [0u8; 32],
+            status: dare.status,
+            rejected_at: now,
+        });
+
+        msg!("Dare {} proof rejected by oracle.", dare_id);
+    }
+
+    emit_cpi!(ProofAttested {
+        dare: ctx.accounts.dare.key(),
+        oracle,
+        submitter: ctx.accounts.daree.key(),
+        proof_hash,
+        approved: approve,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleWithAttestation<'info> {
+    /// Anyone can relay the oracle's verdict
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Must be the daree stored in the dare account (or the submitter being settled) — validated in handler
+    #[account(mut)]
+    pub daree: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, daree.key().as_ref()],
+        bump = daree_stats.bump,
+        constraint = daree_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub daree_stats: Account<'info, UserStats>,
+
+    /// Optional: only needed for PublicBounty dares settled by the challenger
+    #[account(
+        mut,
+        seeds = [SUBMISSION_SEED, dare.key().as_ref(), daree.key().as_ref()],
+        bump = submission.bump,
+    )]
+    pub submission: Option<Account<'info, Submission>>,
+
+    /// Optional: only needed to approve; dares the challenger and daree have completed together
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Dealings::SPACE,
+        seeds = [
+            DEALINGS_SEED,
+            Dealings::lower(&dare.challenger, daree.key),
+            Dealings::higher(&dare.challenger, daree.key),
+        ],
+        bump,
+    )]
+    pub dealings: Option<Account<'info, Dealings>>,
+
+    /// Optional: only needed when rejecting, to stamp the attempt under review
+    #[account(
+        mut,
+        seeds = [PROOF_ATTEMPT_SEED, dare.key().as_ref(), &proof_attempt.index.to_le_bytes()],
+        bump = proof_attempt.bump,
+    )]
+    pub proof_attempt: Option<Account<'info, ProofAttempt>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = treasury @ DareMeError::InvalidTreasury,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury receiving the fee — validated via has_one on config
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: Instructions sysvar, read to find the oracle's Ed25519 signature instruction
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: daree's token account receiving the payout
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = daree,
+        token::token_program = token_program,
+    )]
+    pub daree_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: treasury's token account receiving the fee on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::error::DareMeError;
+use crate::events::*;
+use crate::escrow::Escrow;
 use crate::state::*;
 
 pub fn handler(ctx: Context<SubmitProof>, proof_hash: [u8; 32]) -> Result<()> {
@@ -11,49 +14,186 @@ pub fn handler(ctx: Context<SubmitProof>, proof_hash: [u8; 32]) -> Result<()> {
 
     require!(dare.deadline > now, DareMeError::DareExpired);
     require!(ctx.accounts.submitter.key() != dare.challenger, DareMeError::CannotAcceptOwnDare);
+    require!(!dare.has_judge || ctx.accounts.submitter.key() != dare.judge, DareMeError::InvalidJudge);
+    // Submitting is how a bounty is taken, so its reputation gate applies here
+    if dare.dare_type == DareType::PublicBounty {
+        require!(
+            ctx.accounts.submitter_stats.meets_reputation(dare.min_reputation),
+            DareMeError::ReputationTooLow
+        );
+    }
 
-    match dare.dare_type {
-        DareType::DirectDare => {
-            require!(
-                dare.status == DareStatus::Active || dare.status == DareStatus::Rejected,
-                DareMeError::InvalidDareStatus
-            );
+    // Bounties settled by the challenger take many submissions in parallel, one PDA per submitter
+    let mut new_submitter = false;
+    let mut streak_check_in = None;
+    if dare.takes_submissions() {
+        require!(
+            dare.status == DareStatus::Created
+                || dare.status == DareStatus::Active
+                || dare.status == DareStatus::ProofSubmitted
+                || dare.status == DareStatus::Rejected,
+            DareMeError::InvalidDareStatus
+        );
+
+        let submission = ctx.accounts.submission.as_mut().ok_or(DareMeError::MissingSubmission)?;
+        // A submission older than the dare belongs to a closed dare that used the same address
+        new_submitter = submission.submitter == Pubkey::default() || submission.submitted_at < dare.created_at;
+        if new_submitter {
+            submission.dare = dare.key();
+            submission.submitter = ctx.accounts.submitter.key();
+            submission.bump = ctx.bumps.submission.ok_or(DareMeError::MissingSubmission)?;
+        } else {
+            // Only a rejected submission can be replaced
             require!(
-                dare.has_daree && ctx.accounts.submitter.key() == dare.daree,
-                DareMeError::UnauthorizedDaree
+                submission.status == SubmissionStatus::Rejected,
+                DareMeError::SubmissionPending
             );
         }
-        DareType::PublicBounty => {
-            require!(
-                dare.status == DareStatus::Created || dare.status == DareStatus::Rejected,
-                DareMeError::InvalidDareStatus
-            );
-            // For PublicBounty, set the daree when proof is submitted
-            dare.daree = ctx.accounts.submitter.key();
-            dare.has_daree = true;
+        submission.proof_hash = proof_hash;
+        submission.submitted_at = now;
+        submission.status = SubmissionStatus::Pending;
+        submission.attempt = dare.proof_attempts;
+
+        dare.pending_submissions = dare.pending_submissions
+            .checked_add(1)
+            .ok_or(DareMeError::ArithmeticOverflow)?;
+        dare.status = DareStatus::ProofSubmitted;
+    } else {
+        match dare.dare_type {
+            DareType::DirectDare => {
+                require!(
+                    dare.status == DareStatus::Active || dare.status == DareStatus::Rejected,
+                    DareMeError::InvalidDareStatus
+                );
+                require!(
+                    dare.has_daree && ctx.accounts.submitter.key() == dare.daree,
+                    DareMeError::UnauthorizedDaree
+                );
+
+                // Streaks take one check-in per period; a rejected final check-in can be redone
+                if dare.is_streak() {
+                    let period = dare.streak_period_at(now).ok_or(DareMeError::StreakNotStarted)?;
+                    require!(dare.unsettled_misses(now) == 0, DareMeError::MissedPeriodsUnsettled);
+                    let bit = 1u64 << period;
+                    require!(
+                        dare.checkins & bit == 0 || dare.status == DareStatus::Rejected,
+                        DareMeError::AlreadyCheckedIn
+                    );
+                    dare.checkins |= bit;
+                    streak_check_in = Some(period);
+                }
+            }
+            DareType::PublicBounty => {
+                require!(
+                    dare.status == DareStatus::Created || dare.status == DareStatus::Rejected,
+                    DareMeError::InvalidDareStatus
+                );
+                // For a CommunityVote PublicBounty, the first submitter becomes the daree
+                dare.daree = ctx.accounts.submitter.key();
+                dare.has_daree = true;
+                new_submitter = true;
+            }
+        }
+
+        dare.proof_hash = proof_hash;
+        dare.has_proof = true;
+        // Only a streak's final check-in goes to the challenger for review
+        dare.status = match streak_check_in {
+            Some(period) if period + 1 < dare.streak_periods => DareStatus::Active,
+            _ => DareStatus::ProofSubmitted,
+        };
+
+        // CommunityVote dares open a fresh voting period on every submission
+        if dare.winner_selection == WinnerSelection::CommunityVote {
+            dare.voting_ends_at = now + VOTING_PERIOD;
+            dare.votes_for = 0;
+            dare.votes_against = 0;
         }
     }
 
-    dare.proof_hash = proof_hash;
-    dare.has_proof = true;
-    dare.status = DareStatus::ProofSubmitted;
+    // A bounty is taken by its first submission, which starts any relative completion clock
+    if dare.dare_type == DareType::PublicBounty && dare.accepted_at == 0 {
+        dare.start_completion_clock(now)?;
+    }
+
+    // Every submission gets its own ProofAttempt, so rejected proofs stay on record
+    let attempt = dare.proof_attempts;
+    dare.proof_attempts = dare.proof_attempts
+        .checked_add(1)
+        .ok_or(DareMeError::ArithmeticOverflow)?;
+    let proof_attempt = &mut ctx.accounts.proof_attempt;
+    proof_attempt.dare = dare.key();
+    proof_attempt.submitter = ctx.accounts.submitter.key();
+    proof_attempt.index = attempt;
+    proof_attempt.proof_hash = proof_hash;
+    proof_attempt.submitted_at = now;
+    proof_attempt.rejected_at = 0;
+    proof_attempt.has_rejection_reason = false;
+    proof_attempt.rejection_reason_hash = [0u8; 32];
+    proof_attempt.bump = ctx.bumps.proof_attempt;
+    // The dispute window runs from the latest submission
+    dare.proof_submitted_at = now;
+
+    // A bounty's daree only steps forward here, so this is where their counter-stake is locked
+    if dare.dare_type == DareType::PublicBounty && !dare.takes_submissions() && dare.stake_due() {
+        let stake = dare.daree_stake;
+        let escrow = Escrow::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.vault,
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        let received = escrow.deposit(
+            &ctx.accounts.submitter,
+            ctx.accounts.submitter_token_account.as_ref(),
+            stake,
+        )?;
+        ctx.accounts.dare.stake_held = received;
+    }
 
     // Initialize submitter stats
     let stats = &mut ctx.accounts.submitter_stats;
     if stats.user == Pubkey::default() {
         stats.user = ctx.accounts.submitter.key();
         stats.bump = ctx.bumps.submitter_stats;
+        stats.version = USER_STATS_VERSION;
     }
-    
+
     // For PublicBounty, also increment dares_accepted since they're accepting by submitting
-    if dare.dare_type == DareType::PublicBounty {
+    if new_submitter {
         stats.dares_accepted += 1;
     }
 
+    let dare = &ctx.accounts.dare;
+    emit_cpi!(ProofSubmitted {
+        dare: dare.key(),
+        challenger: dare.challenger,
+        submitter: ctx.accounts.submitter.key(),
+        proof_hash,
+        attempt,
+        stake: dare.stake_held,
+        status: dare.status,
+        submitted_at: now,
+    });
+
+    if let Some(period) = streak_check_in {
+        emit_cpi!(StreakCheckIn {
+            dare: dare.key(),
+            daree: dare.daree,
+            period,
+            checkins: dare.checkins,
+            status: dare.status,
+            checked_in_at: now,
+        });
+    }
+
     msg!("Proof submitted for dare {} by {}", dare.dare_id, ctx.accounts.submitter.key());
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SubmitProof<'info> {
     #[account(mut)]
@@ -63,17 +203,79 @@ pub struct SubmitProof<'info> {
         mut,
         seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
         bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub dare: Account<'info, Dare>,
 
+    /// Blocks new money from entering while the program is paused
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ DareMeError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         init_if_needed,
         payer = submitter,
         space = UserStats::SPACE,
         seeds = [USER_STATS_SEED, submitter.key().as_ref()],
         bump,
+        constraint = submitter_stats.is_current() @ DareMeError::AccountNotMigrated,
     )]
     pub submitter_stats: Account<'info, UserStats>,
 
+    /// Optional: only needed for PublicBounty dares settled by the challenger
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = Submission::SPACE,
+        seeds = [SUBMISSION_SEED, dare.key().as_ref(), submitter.key().as_ref()],
+        bump,
+    )]
+    pub submission: Option<Account<'info, Submission>>,
+
+    /// Indexed by the dare's attempt counter. Only an address left over from a closed dare
+    /// can already exist, and it is overwritten.
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = ProofAttempt::SPACE,
+        seeds = [PROOF_ATTEMPT_SEED, dare.key().as_ref(), &dare.proof_attempts.to_le_bytes()],
+        bump,
+    )]
+    pub proof_attempt: Account<'info, ProofAttempt>,
+
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Optional: only for token bounties with a counter-stake
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: submitter's token account funding the counter-stake
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = submitter,
+        token::token_program = token_program,
+    )]
+    pub submitter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::escrow::Escrow;
+use crate::events::*;
+use crate::state::*;
+
+/// Permissionless crank: settle a CommunityVote dare once its voting period has closed.
+/// The proof stands unless at least VOTE_QUORUM votes were cast and a majority rejected it.
+/// Approved → escrow released to daree, less the protocol fee. Rejected → escrow refunded to challenger.
+pub fn handler(ctx: Context<TallyVotes>) -> Result<()> {
+    // Capture values before mutable borrow
+    let has_mint = ctx.accounts.dare.has_mint;
+    let dare_id = ctx.accounts.dare.dare_id;
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let escrow = Escrow::new(
+        &ctx.accounts.dare,
+        &ctx.accounts.vault,
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let dare = &mut ctx.accounts.dare;
+
+    require!(
+        dare.winner_selection == WinnerSelection::CommunityVote,
+        DareMeError::NotCommunityVote
+    );
+    require!(dare.status == DareStatus::ProofSubmitted, DareMeError::InvalidDareStatus);
+    require!(now >= dare.voting_ends_at, DareMeError::VotingActive);
+
+    let total_votes = dare.votes_for
+        .checked_add(dare.votes_against)
+        .ok_or(DareMeError::ArithmeticOverflow)?;
+    let rejected = total_votes >= VOTE_QUORUM && dare.votes_against > dare.votes_for;
+
+    if rejected {
+        // Verify recipient is the challenger (refund)
+        require!(
+            ctx.accounts.recipient.key() == dare.challenger,
+            DareMeError::UnauthorizedChallenger
+        );
+
+        dare.status = DareStatus::Failed;
+
+        // Refund the challenger's share of escrow from vault using CPI with PDA signer;
+        // backers claim theirs separately. The daree's counter-stake is forfeited to the challenger.
+        let forfeited = dare.stake_held;
+        let released = escrow.releasable(dare.start_refund(escrow.balance()?)?)?;
+        escrow.release(
+            &ctx.accounts.recipient.to_account_info(),
+            ctx.accounts.recipient_token_account.as_ref(),
+            released,
+        )?;
+
+        // Update stats
+        if !has_mint {
+            let challenger_stats = &mut ctx.accounts.challenger_stats;
+            challenger_stats.total_spent = challenger_stats.total_spent
+                .saturating_sub(released.saturating_sub(forfeited));
+        }
+        ctx.accounts.daree_stats.record_failure();
+
+        emit_cpi!(ProofRejected {
+            dare: dare.key(),
+            challenger: dare.challenger,
+            judge: dare.judge,
+            submitter: dare.daree,
+            proof_hash: dare.proof_hash,
+            attempt: dare.proof_attempts.saturating_sub(1),
+            reason_hash: [0u8; 32],
+            status: dare.status,
+            rejected_at: now,
+        });
+
+        msg!(
+            "Dare {} rejected by vote ({} for, {} against). {} refunded to challenger.",
+            dare_id, dare.votes_for, dare.votes_against, released
+        );
+    } else {
+        // Verify recipient is the daree (approve)
+        require!(
+            dare.has_daree && ctx.accounts.recipient.key() == dare.daree,
+            DareMeError::UnauthorizedDaree
+        );
+
+        // Release escrow (reward plus the daree's own stake) from vault to daree using CPI with PDA signer.
+        // The protocol fee comes out of the reward, exactly as on the judged approval path.
+        let released = escrow.balance()?;
+        let reward = dare.reward_portion(released);
+        let fee = ctx.accounts.config.fee_on(reward)?;
+        escrow.release(
+            &ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.treasury_token_account.as_ref(),
+            fee,
+        )?;
+        escrow.release(
+            &ctx.accounts.recipient.to_account_info(),
+            ctx.accounts.recipient_token_account.as_ref(),
+            released - fee,
+        )?;
+        let earned = reward - fee;
+        let decimals = escrow.decimals();
+        dare.complete(now);
+
+        // Update daree stats (they completed it)
+        let dealings = ctx.accounts.dealings.as_mut().ok_or(DareMeError::MissingDealings)?;
+        dealings.open(&dare.challenger, &dare.daree, ctx.bumps.dealings.ok_or(DareMeError::MissingDealings)?);
+        let daree_stats = &mut ctx.accounts.daree_stats;
+        daree_stats.dares_completed += 1;
+        if !has_mint {
+            daree_stats.total_earned = daree_stats.total_earned
+                .checked_add(earned)
+                .ok_or(DareMeError::ArithmeticOverflow)?;
+        }
+        daree_stats.credit_reputation(dare, earned, decimals, true, dealings);
+
+        emit_cpi!(DareApproved {
+            dare: dare.key(),
+            challenger: dare.challenger,
+            judge: dare.judge,
+            daree: dare.daree,
+            amount: released - fee,
+            fee,
+            judge_fee: 0,
+            winners_paid: dare.winners_paid,
+            status: dare.status,
+            approved_at: now,
+        });
+
+        msg!(
+            "Dare {} approved by vote ({} for, {} against). {} released to daree ({} fee).",
+            dare_id, dare.votes_for, dare.votes_against, released - fee, fee
+        );
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TallyVotes<'info> {
+    /// Anyone can call this (permissionless crank)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DARE_SEED, dare.challenger.as_ref(), &dare.dare_id.to_le_bytes()],
+        bump = dare.bump,
+        constraint = dare.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub dare: Account<'info, Dare>,
+
+    /// CHECK: Vault PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, dare.key().as_ref()],
+        bump = dare.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Must be daree (approved) or challenger (rejected) — validated in handler
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, dare.challenger.as_ref()],
+        bump = challenger_stats.bump,
+        constraint = challenger_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub challenger_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, dare.daree.as_ref()],
+        bump = daree_stats.bump,
+        constraint = daree_stats.is_current() @ DareMeError::AccountNotMigrated,
+    )]
+    pub daree_stats: Account<'info, UserStats>,
+
+    /// Optional: only needed when the proof stands; dares the challenger and daree have completed together
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Dealings::SPACE,
+        seeds = [
+            DEALINGS_SEED,
+            Dealings::lower(&dare.challenger, &dare.daree),
+            Dealings::higher(&dare.challenger, &dare.daree),
+        ],
+        bump,
+    )]
+    pub dealings: Option<Account<'info, Dealings>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = treasury @ DareMeError::InvalidTreasury,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury receiving the fee — validated via has_one on config
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    /// Optional: only for token dares
+    #[account(address = dare.mint @ DareMeError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Optional: vault's associated token account holding the escrowed tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: recipient's token account receiving the payout
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = recipient,
+        token::token_program = token_program,
+    )]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: treasury's token account receiving the fee on token dares
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin sets the key that resolves disputes. Use a multisig to have an arbiter set rule together.
+pub fn handler(ctx: Context<UpdateArbiter>, arbiter: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_arbiter = config.arbiter;
+    config.arbiter = arbiter;
+
+    emit_cpi!(ArbiterUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_arbiter,
+        arbiter,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Arbiter updated: {}", arbiter);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateArbiter<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin changes the range of dispute windows new dares can pick. Existing dares keep their own window.
+pub fn handler(ctx: Context<UpdateDisputeWindowBounds>, min_window: i64, max_window: i64) -> Result<()> {
+    require!(
+        MIN_DISPUTE_WINDOW <= min_window && min_window <= max_window && max_window <= MAX_DISPUTE_WINDOW,
+        DareMeError::InvalidDisputeWindow
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.min_dispute_window = min_window;
+    config.max_dispute_window = max_window;
+
+    emit_cpi!(DisputeWindowBoundsUpdated {
+        admin: ctx.accounts.admin.key(),
+        min_window,
+        max_window,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Dispute window bounds updated: {}s to {}s", min_window, max_window);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateDisputeWindowBounds<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
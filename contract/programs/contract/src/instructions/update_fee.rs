@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin changes the protocol fee. Applies to every payout from now on, including on existing dares.
+pub fn handler(ctx: Context<UpdateFee>, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, DareMeError::FeeTooHigh);
+
+    let config = &mut ctx.accounts.config;
+    let old_fee_bps = config.fee_bps;
+    config.fee_bps = fee_bps;
+
+    emit_cpi!(FeeUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_fee_bps,
+        fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Protocol fee updated: {} -> {} bps", old_fee_bps, fee_bps);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
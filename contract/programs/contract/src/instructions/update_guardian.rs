@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin sets the guardian key allowed to pause/unpause (Pubkey::default() removes it)
+pub fn handler(ctx: Context<UpdateGuardian>, guardian: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.guardian = guardian;
+
+    emit_cpi!(GuardianUpdated {
+        admin: ctx.accounts.admin.key(),
+        guardian,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Guardian updated: {}", guardian);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateGuardian<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin sets the key whose ed25519 attestations settle proofs. Pubkey::default() turns oracle settlement off.
+pub fn handler(ctx: Context<UpdateOracle>, oracle: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_oracle = config.oracle;
+    config.oracle = oracle;
+
+    emit_cpi!(OracleUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_oracle,
+        oracle,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Oracle updated: {}", oracle);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateOracle<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
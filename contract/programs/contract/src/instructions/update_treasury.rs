@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::DareMeError;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin points fee collection at a new treasury
+pub fn handler(ctx: Context<UpdateTreasury>, treasury: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_treasury = config.treasury;
+    config.treasury = treasury;
+
+    emit_cpi!(TreasuryUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_treasury,
+        treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Treasury updated: {}", treasury);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTreasury<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ DareMeError::UnauthorizedAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
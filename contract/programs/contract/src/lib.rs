@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
 
+pub mod attestation;
 pub mod constants;
 pub mod error;
+pub mod escrow;
+pub mod events;
 pub mod instructions;
+pub mod migration;
 pub mod state;
 
 use instructions::*;
@@ -14,8 +18,11 @@ declare_id!("8Vg3ximsFxoaEveSLQNe49i8tkSaeNubnxa54ypwXiD8");
 pub mod contract {
     use super::*;
 
-    /// Create a new dare with SOL escrow
-    /// `target_daree` — set to Pubkey::default() for open dares, or a specific pubkey for targeted DirectDares
+    /// Create a new dare with SOL or SPL token escrow. Returns the dare's ID.
+    /// `dare_id` — 0 for the program to assign the next ID from the challenger's counter, or a client-picked ID
+    /// Pass the `mint` and token accounts to escrow an SPL token instead of SOL (plus `allowed_mint` if it has a freeze authority)
+    /// `params` — target, payout schedule, stake, streak, judge and timing settings (see CreateDareParams)
+    #[allow(clippy::too_many_arguments)]
     pub fn create_dare(
         ctx: Context<CreateDare>,
         dare_id: u64,
@@ -24,12 +31,12 @@ pub mod contract {
         deadline: i64,
         dare_type: DareType,
         winner_selection: WinnerSelection,
-        target_daree: Pubkey,
-    ) -> Result<()> {
-        instructions::create_dare::handler(ctx, dare_id, description_hash, amount, deadline, dare_type, winner_selection, target_daree)
+        params: CreateDareParams,
+    ) -> Result<u64> {
+        instructions::create_dare::handler(ctx, dare_id, description_hash, amount, deadline, dare_type, winner_selection, params)
     }
 
-    /// Accept a P2P dare (DirectDare only)
+    /// Accept a P2P dare (DirectDare only), depositing the daree's counter-stake if the dare requires one
     pub fn accept_dare(ctx: Context<AcceptDare>) -> Result<()> {
         instructions::accept_dare::handler(ctx)
     }
@@ -39,14 +46,14 @@ pub mod contract {
         instructions::submit_proof::handler(ctx, proof_hash)
     }
 
-    /// Challenger approves proof and releases escrow to daree
+    /// Challenger (or the dare's judge) approves proof and releases escrow to daree
     pub fn approve_dare(ctx: Context<ApproveDare>) -> Result<()> {
         instructions::approve_dare::handler(ctx)
     }
 
-    /// Challenger rejects proof (daree can re-submit)
-    pub fn reject_dare(ctx: Context<RejectDare>) -> Result<()> {
-        instructions::reject_dare::handler(ctx)
+    /// Challenger (or the dare's judge) rejects proof (daree can re-submit), optionally with a reason hash
+    pub fn reject_dare(ctx: Context<RejectDare>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::reject_dare::handler(ctx, reason_hash)
     }
 
     /// Challenger cancels dare before acceptance (refund)
@@ -54,13 +61,140 @@ pub mod contract {
         instructions::cancel_dare::handler(ctx)
     }
 
-    /// Permissionless crank: handle expired dares
+    /// Permissionless crank: handle expired dares, missed streak check-ins and disputes the arbiter never ruled on
     pub fn expire_dare(ctx: Context<ExpireDare>) -> Result<()> {
         instructions::expire_dare::handler(ctx)
     }
 
-    /// Targeted daree refuses a DirectDare (refund to challenger)
+    /// Targeted daree refuses a DirectDare, or an accepted daree gives up (refund to challenger, stake forfeited)
     pub fn refuse_dare(ctx: Context<RefuseDare>) -> Result<()> {
         instructions::refuse_dare::handler(ctx)
     }
+
+    /// Cast a community vote on a CommunityVote dare's proof (one per wallet)
+    pub fn cast_vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+        instructions::cast_vote::handler(ctx, approve)
+    }
+
+    /// Permissionless crank: settle a CommunityVote dare after voting closes
+    pub fn tally_votes(ctx: Context<TallyVotes>) -> Result<()> {
+        instructions::tally_votes::handler(ctx)
+    }
+
+    /// Add funds to an open dare's escrow as a third-party backer
+    pub fn back_dare(ctx: Context<BackDare>, amount: u64) -> Result<()> {
+        instructions::back_dare::handler(ctx, amount)
+    }
+
+    /// Backer claims their pro-rata refund after a dare is cancelled, refused, expired or voted down
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        instructions::claim_refund::handler(ctx)
+    }
+
+    /// Close a finished dare: sweep vault dust and return rent to the challenger (permissionless)
+    pub fn close_dare(ctx: Context<CloseDare>) -> Result<()> {
+        instructions::close_dare::handler(ctx)
+    }
+
+    /// Challenger or daree proposes a later deadline (within MAX_DEADLINE_DURATION of creation, or of acceptance for relative deadlines)
+    pub fn propose_deadline_extension(ctx: Context<ProposeDeadlineExtension>, new_deadline: i64) -> Result<()> {
+        instructions::propose_deadline_extension::handler(ctx, new_deadline)
+    }
+
+    /// The other party accepts the proposed deadline
+    pub fn accept_deadline_extension(ctx: Context<AcceptDeadlineExtension>) -> Result<()> {
+        instructions::accept_deadline_extension::handler(ctx)
+    }
+
+    /// Challenger adds more to the reward of a Created or Active dare
+    pub fn increase_stake(ctx: Context<IncreaseStake>, amount: u64) -> Result<()> {
+        instructions::increase_stake::handler(ctx, amount)
+    }
+
+    /// Anyone reveals the plaintext description, checked on-chain against description_hash
+    pub fn reveal_description(ctx: Context<RevealDescription>, description: String) -> Result<()> {
+        instructions::reveal_description::handler(ctx, description)
+    }
+
+    /// One-time setup of the global config (admin, treasury, protocol fee)
+    pub fn initialize_config(ctx: Context<InitializeConfig>, treasury: Pubkey, fee_bps: u16) -> Result<()> {
+        instructions::initialize_config::handler(ctx, treasury, fee_bps)
+    }
+
+    /// Admin updates the protocol fee (capped at MAX_FEE_BPS)
+    pub fn update_fee(ctx: Context<UpdateFee>, fee_bps: u16) -> Result<()> {
+        instructions::update_fee::handler(ctx, fee_bps)
+    }
+
+    /// Admin updates the treasury that collects protocol fees
+    pub fn update_treasury(ctx: Context<UpdateTreasury>, treasury: Pubkey) -> Result<()> {
+        instructions::update_treasury::handler(ctx, treasury)
+    }
+
+    /// Admin sets the range dares can pick their dispute window from
+    pub fn update_dispute_window_bounds(ctx: Context<UpdateDisputeWindowBounds>, min_window: i64, max_window: i64) -> Result<()> {
+        instructions::update_dispute_window_bounds::handler(ctx, min_window, max_window)
+    }
+
+    /// Admin accepts a mint with a freeze authority (e.g. USDC) for new token dares
+    pub fn allow_mint(ctx: Context<AllowMint>) -> Result<()> {
+        instructions::allow_mint::handler(ctx)
+    }
+
+    /// Admin removes a mint from the freeze-authority allowlist
+    pub fn disallow_mint(ctx: Context<DisallowMint>) -> Result<()> {
+        instructions::disallow_mint::handler(ctx)
+    }
+
+    /// Admin sets the guardian key that can pause/unpause
+    pub fn update_guardian(ctx: Context<UpdateGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::update_guardian::handler(ctx, guardian)
+    }
+
+    /// Admin or guardian pauses new dares, acceptances, submissions, backing and top-ups (exits stay open)
+    pub fn pause(ctx: Context<SetPaused>) -> Result<()> {
+        instructions::set_paused::handler(ctx, true)
+    }
+
+    /// Admin or guardian lifts the pause
+    pub fn unpause(ctx: Context<SetPaused>) -> Result<()> {
+        instructions::set_paused::handler(ctx, false)
+    }
+
+    /// Admin sets the arbiter that resolves disputes
+    pub fn update_arbiter(ctx: Context<UpdateArbiter>, arbiter: Pubkey) -> Result<()> {
+        instructions::update_arbiter::handler(ctx, arbiter)
+    }
+
+    /// Admin sets the oracle whose signed verdicts can settle proofs
+    pub fn update_oracle(ctx: Context<UpdateOracle>, oracle: Pubkey) -> Result<()> {
+        instructions::update_oracle::handler(ctx, oracle)
+    }
+
+    /// Anyone settles a proof with the oracle's ed25519-signed verdict, passed as the previous instruction
+    pub fn settle_with_attestation(ctx: Context<SettleWithAttestation>, approve: bool) -> Result<()> {
+        instructions::settle_with_attestation::handler(ctx, approve)
+    }
+
+    /// Daree escalates a rejected proof to the arbiter (freezes escrow)
+    pub fn dispute_rejection(ctx: Context<DisputeRejection>) -> Result<()> {
+        instructions::dispute_rejection::handler(ctx)
+    }
+
+    /// Arbiter rules on a disputed dare: pay the daree, refund the challenger, or split
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, ruling: DisputeRuling) -> Result<()> {
+        instructions::resolve_dispute::handler(ctx, ruling)
+    }
+
+    /// Move a dare created before accounts were versioned onto the current layout (anyone can call; payer covers rent).
+    /// Pass the dare's first `proof_attempt` PDA if it has a proof under review, and the submitter's `submission` PDA
+    /// too if it is a challenger-select bounty
+    pub fn migrate_dare(ctx: Context<MigrateDare>) -> Result<()> {
+        instructions::migrate_dare::handler(ctx)
+    }
+
+    /// Move a user's stats onto the current layout (anyone can call; payer covers rent)
+    pub fn migrate_user_stats(ctx: Context<MigrateUserStats>) -> Result<()> {
+        instructions::migrate_user_stats::handler(ctx)
+    }
 }
\ No newline at end of file
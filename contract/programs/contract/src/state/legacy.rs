@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use super::*;
+
+/// Dare as laid out before accounts were versioned (version 0, LEGACY_DARE_SPACE bytes).
+/// Only read by migrate_dare.
+#[derive(AnchorDeserialize)]
+pub struct LegacyDare {
+    pub challenger: Pubkey,
+    pub daree: Pubkey,
+    pub has_daree: bool,
+    pub dare_id: u64,
+    pub description_hash: [u8; 32],
+    pub amount: u64,
+    pub status: DareStatus,
+    pub dare_type: DareType,
+    pub winner_selection: WinnerSelection,
+    pub proof_hash: [u8; 32],
+    pub has_proof: bool,
+    pub created_at: i64,
+    pub deadline: i64,
+    pub accepted_at: i64,
+    pub completed_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+/// UserStats before accounts were versioned (version 0, LEGACY_USER_STATS_SPACE bytes).
+/// Only read by migrate_user_stats.
+#[derive(AnchorDeserialize)]
+pub struct LegacyUserStats {
+    pub user: Pubkey,
+    pub dares_created: u32,
+    pub dares_accepted: u32,
+    pub dares_completed: u32,
+    pub dares_failed: u32,
+    pub total_earned: u64,
+    pub total_spent: u64,
+    pub bump: u8,
+}
+
+impl Dare {
+    /// Rebuilds a version 0 dare on the current layout. Everything the old program didn't track
+    /// gets the value that keeps its old behaviour: a SOL escrow paid to a single winner, no stake,
+    /// backers, streak or judge, an absolute deadline, and a proof under review that auto-approves
+    /// DISPUTE_WINDOW after the deadline, as it did before.
+    pub fn from_legacy(legacy: LegacyDare, now: i64) -> Self {
+        let mut payout_bps = [0u16; MAX_WINNERS];
+        payout_bps[0] = BPS_DENOMINATOR as u16;
+        let under_review = legacy.status == DareStatus::ProofSubmitted;
+
+        Self {
+            challenger: legacy.challenger,
+            daree: legacy.daree,
+            has_daree: legacy.has_daree,
+            dare_id: legacy.dare_id,
+            description_hash: legacy.description_hash,
+            amount: legacy.amount,
+            mint: Pubkey::default(),
+            has_mint: false,
+            status: legacy.status,
+            dare_type: legacy.dare_type,
+            winner_selection: legacy.winner_selection,
+            proof_hash: legacy.proof_hash,
+            has_proof: legacy.has_proof,
+            created_at: legacy.created_at,
+            deadline: legacy.deadline,
+            accepted_at: legacy.accepted_at,
+            completed_at: legacy.completed_at,
+            bump: legacy.bump,
+            vault_bump: legacy.vault_bump,
+            // Old community-vote dares never had a vote, so one opens now
+            voting_ends_at: if under_review && legacy.winner_selection == WinnerSelection::CommunityVote {
+                now + VOTING_PERIOD
+            } else {
+                0
+            },
+            votes_for: 0,
+            votes_against: 0,
+            pending_submissions: 0,
+            payout_bps,
+            winner_count: 1,
+            winners_paid: if legacy.status == DareStatus::Completed { 1 } else { 0 },
+            backed_amount: 0,
+            backer_count: 0,
+            backers_refunded: 0,
+            refund_pool: 0,
+            daree_stake: 0,
+            stake_held: 0,
+            disputed_at: 0,
+            proposed_deadline: 0,
+            proposed_by: Pubkey::default(),
+            streak_period: 0,
+            streak_periods: 0,
+            miss_policy: MissPolicy::EndDare,
+            checkins: 0,
+            periods_settled: 0,
+            missed_periods: 0,
+            judge: Pubkey::default(),
+            has_judge: false,
+            judge_fee_bps: 0,
+            description_revealed: false,
+            revealed_at: 0,
+            proof_attempts: 0,
+            proof_submitted_at: if under_review { legacy.deadline } else { 0 },
+            dispute_window: DISPUTE_WINDOW,
+            accept_by: legacy.deadline,
+            time_to_complete: 0,
+            version: DARE_VERSION,
+            min_reputation: 0,
+            challenger_reputation: 0,
+            rejected_at: if legacy.status == DareStatus::Rejected { legacy.deadline } else { 0 },
+            reserved: [0u8; 40],
+        }
+    }
+}
+
+impl UserStats {
+    /// Rebuilds version 0 stats on the current layout; counters the old program didn't keep start at zero
+    pub fn from_legacy(legacy: LegacyUserStats) -> Self {
+        Self {
+            user: legacy.user,
+            dares_created: legacy.dares_created,
+            dares_accepted: legacy.dares_accepted,
+            dares_completed: legacy.dares_completed,
+            dares_failed: legacy.dares_failed,
+            total_earned: legacy.total_earned,
+            total_spent: legacy.total_spent,
+            bump: legacy.bump,
+            milestones_completed: 0,
+            dares_judged: 0,
+            judge_fees_earned: 0,
+            next_dare_id: 0,
+            version: USER_STATS_VERSION,
+            reputation: 0,
+            dares_ghosted: 0,
+            reserved: [0u8; 20],
+        }
+    }
+}
@@ -1,5 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::*;
+use crate::error::DareMeError;
+
+mod legacy;
+mod versioned;
+pub use legacy::*;
+use versioned::versioned_account;
+
 // ============================================================================
 // Enums
 // ============================================================================
@@ -14,6 +22,30 @@ pub enum DareStatus {
     Cancelled,
     Rejected,
     Refused,
+    Failed,
+    Disputed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubmissionStatus {
+    Pending,
+    Rejected,
+    Approved,
+}
+
+/// Arbiter's decision on a disputed rejection
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisputeRuling {
+    PayDaree,
+    RefundChallenger,
+    Split { daree_bps: u16 },
+}
+
+/// What a missed check-in costs on a streak dare
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MissPolicy {
+    EndDare,
+    ForfeitSlice,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -28,13 +60,48 @@ pub enum WinnerSelection {
     CommunityVote,
 }
 
+// ============================================================================
+// Instruction arguments
+// ============================================================================
+
+/// create_dare's optional settings; the zero value of each field (and `EndDare`) leaves it off
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CreateDareParams {
+    /// Pubkey::default() for open dares, or a specific pubkey for targeted DirectDares
+    pub target_daree: Pubkey,
+    /// Empty for a single winner, or ranked shares in basis points (e.g. [5000, 3000, 2000]) for PublicBounty,
+    /// or milestone shares released one approval at a time for DirectDare
+    pub payout_shares_bps: Vec<u16>,
+    /// Counter-stake the daree must lock when accepting (0 for none); forfeited to the challenger on failure
+    pub daree_stake: u64,
+    /// Length of each streak check-in period, in seconds
+    pub streak_period: i64,
+    /// 0 for a one-off dare, or the number of back-to-back `streak_period` check-ins ending at the deadline
+    pub streak_periods: u8,
+    /// Whether a missed check-in ends the dare or forfeits one slice of the escrow
+    pub miss_policy: MissPolicy,
+    /// Pubkey::default() for the challenger to judge, or a third party who approves/rejects instead
+    pub judge: Pubkey,
+    /// The judge's cut of the reward on settlement
+    pub judge_fee_bps: u16,
+    /// Seconds each proof stays under review before it auto-approves (0 for the default),
+    /// within the config's bounds
+    pub dispute_window: i64,
+    /// 0 for `deadline` to be the completion deadline, or seconds the daree gets from acceptance
+    /// (first submission for bounties), making `deadline` the accept-by time
+    pub time_to_complete: i64,
+    /// 0 for anyone, or the reputation score a daree (or bounty submitter) needs to take the dare
+    pub min_reputation: i64,
+}
+
 // ============================================================================
 // Accounts
 // ============================================================================
 
 /// Main Dare account — stores all dare metadata and state on-chain
 /// Seeds: ["dare", challenger.key(), dare_id.to_le_bytes()]
-#[account]
+// Versioned: `versioned_account!` below stands in for `#[account]`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct Dare {
     pub challenger: Pubkey,           // 32
     pub daree: Pubkey,                // 32
@@ -42,6 +109,8 @@ pub struct Dare {
     pub dare_id: u64,                 // 8
     pub description_hash: [u8; 32],   // 32
     pub amount: u64,                  // 8
+    pub mint: Pubkey,                 // 32
+    pub has_mint: bool,               // 1
     pub status: DareStatus,           // 1
     pub dare_type: DareType,          // 1
     pub winner_selection: WinnerSelection, // 1
@@ -53,16 +122,340 @@ pub struct Dare {
     pub completed_at: i64,            // 8
     pub bump: u8,                     // 1
     pub vault_bump: u8,               // 1
+    pub voting_ends_at: i64,          // 8
+    pub votes_for: u32,               // 4
+    pub votes_against: u32,           // 4
+    pub pending_submissions: u32,     // 4
+    pub payout_bps: [u16; MAX_WINNERS], // 10
+    pub winner_count: u8,             // 1
+    pub winners_paid: u8,             // 1
+    pub backed_amount: u64,           // 8
+    pub backer_count: u32,            // 4
+    pub backers_refunded: u32,        // 4
+    pub refund_pool: u64,             // 8
+    pub daree_stake: u64,             // 8
+    pub stake_held: u64,              // 8
+    pub disputed_at: i64,             // 8
+    pub proposed_deadline: i64,       // 8
+    pub proposed_by: Pubkey,          // 32
+    pub streak_period: i64,           // 8
+    pub streak_periods: u8,           // 1
+    pub miss_policy: MissPolicy,      // 1
+    pub checkins: u64,                // 8
+    pub periods_settled: u8,          // 1
+    pub missed_periods: u8,           // 1
+    pub judge: Pubkey,                // 32
+    pub has_judge: bool,              // 1
+    pub judge_fee_bps: u16,           // 2
+    pub description_revealed: bool,   // 1
+    pub revealed_at: i64,             // 8
+    pub proof_attempts: u32,          // 4
+    pub proof_submitted_at: i64,      // 8
+    pub dispute_window: i64,          // 8
+    pub accept_by: i64,               // 8
+    pub time_to_complete: i64,        // 8
+    pub version: u8,                  // 1
+    /// Reputation a daree needs to accept or submit to the dare; 0 for anyone
+    pub min_reputation: i64,          // 8
+    /// Challenger's reputation when the dare was created, which weighs the daree's reputation credit
+    pub challenger_reputation: i64,   // 8
+    /// When the proof was last rejected; the daree's dispute window runs from here
+    pub rejected_at: i64,             // 8
+    /// Zeroed space for fields added by later versions, so older accounts keep deserializing until migrated
+    pub reserved: [u8; 40],           // 40
 }
 
+versioned_account!(Dare, [99, 27, 218, 204, 253, 181, 17, 54]);
+
 impl Dare {
-    // 8 (discriminator) + 183 fields = 191
-    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 32 + 8 + 1 + 1 + 1 + 32 + 1 + 8 + 8 + 8 + 8 + 1 + 1;
+    // 8 (discriminator) + 501 fields = 509
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 32 + 8 + 32 + 1 + 1 + 1 + 1 + 32 + 1 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 4 + 4 + 4
+        + 2 * MAX_WINNERS + 1 + 1 + 8 + 4 + 4 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 1 + 1 + 8 + 1 + 1 + 32 + 1 + 2 + 1 + 8 + 4 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 40;
+
+    /// PublicBounty dares settled by the challenger collect one Submission PDA per submitter
+    /// instead of a single proof on the Dare itself.
+    pub fn takes_submissions(&self) -> bool {
+        self.dare_type == DareType::PublicBounty
+            && self.winner_selection == WinnerSelection::ChallengerSelect
+    }
+
+    /// Last moment the daree can dispute a rejection: the dispute window after the rejection.
+    /// Dares rejected before rejected_at was tracked fall back to the deadline, as they used to.
+    pub fn dispute_closes_at(&self) -> i64 {
+        let rejected_at = if self.rejected_at > 0 { self.rejected_at } else { self.deadline };
+        rejected_at + self.dispute_window
+    }
+
+    /// Marks a pending submission as the next winner and makes its submitter the daree.
+    pub fn award_submission(&mut self, submission: &mut Submission) -> Result<()> {
+        require!(submission.status == SubmissionStatus::Pending, DareMeError::InvalidSubmission);
+        // Left over from a closed dare that used the same address
+        require!(submission.submitted_at >= self.created_at, DareMeError::InvalidSubmission);
+        submission.status = SubmissionStatus::Approved;
+
+        self.daree = submission.submitter;
+        self.has_daree = true;
+        self.proof_hash = submission.proof_hash;
+        self.has_proof = true;
+        self.pending_submissions = self.pending_submissions.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Amount owed to the next winner: their share of `amount`, rounded down.
+    /// The final winner takes everything left in escrow, so rounding dust never stays behind.
+    pub fn next_payout(&self, escrow_balance: u64) -> Result<u64> {
+        if self.winners_paid.saturating_add(1) >= self.winner_count {
+            return Ok(escrow_balance);
+        }
+
+        let share_bps = self.payout_bps[self.winners_paid as usize] as u128;
+        let share = (self.amount as u128)
+            .checked_mul(share_bps)
+            .ok_or(DareMeError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let share = u64::try_from(share).map_err(|_| DareMeError::ArithmeticOverflow)?;
+        Ok(share.min(escrow_balance))
+    }
+
+    /// Whether the account is on the current layout; older ones must be migrated first
+    pub fn is_current(&self) -> bool {
+        self.version == DARE_VERSION
+    }
+
+    pub fn has_relative_deadline(&self) -> bool {
+        self.time_to_complete > 0
+    }
+
+    /// Starts the completion clock for a dare with a relative deadline. Until then `deadline`
+    /// holds the accept-by time; from here on it is `accepted_at + time_to_complete`.
+    pub fn start_completion_clock(&mut self, now: i64) -> Result<()> {
+        self.accepted_at = now;
+        if self.has_relative_deadline() {
+            self.deadline = now
+                .checked_add(self.time_to_complete)
+                .ok_or(DareMeError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Latest deadline an extension can move to: MAX_DEADLINE_DURATION from creation,
+    /// or from acceptance when the completion time is relative
+    pub fn max_deadline(&self) -> i64 {
+        let start = if self.has_relative_deadline() { self.accepted_at } else { self.created_at };
+        start + MAX_DEADLINE_DURATION
+    }
+
+    /// Whether `key` is the challenger or daree, i.e. a party to a deadline extension
+    pub fn is_party(&self, key: &Pubkey) -> bool {
+        *key == self.challenger || (self.has_daree && *key == self.daree)
+    }
+
+    /// Whether `key` decides on proofs: the judge if the dare has one, otherwise the challenger
+    pub fn can_judge(&self, key: &Pubkey) -> bool {
+        if self.has_judge {
+            *key == self.judge
+        } else {
+            *key == self.challenger
+        }
+    }
+
+    /// Judge's cut of a reward of `amount`, rounded down (0 without a judge)
+    pub fn judge_fee_on(&self, amount: u64) -> Result<u64> {
+        if !self.has_judge {
+            return Ok(0);
+        }
+        let fee = (amount as u128)
+            .checked_mul(self.judge_fee_bps as u128)
+            .ok_or(DareMeError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        u64::try_from(fee).map_err(|_| DareMeError::ArithmeticOverflow.into())
+    }
+
+    /// Whether the daree still owes the counter-stake before they can take part
+    pub fn stake_due(&self) -> bool {
+        self.daree_stake > 0 && self.stake_held == 0
+    }
+
+    /// DirectDares with a multi-share schedule pay the same daree one milestone at a time
+    pub fn is_milestone_dare(&self) -> bool {
+        !self.takes_submissions() && self.winner_count > 1
+    }
+
+    /// Part of a payout that is reward rather than the daree's own stake coming back.
+    /// Only the final payout sweeps escrow, so earlier milestones never include the stake.
+    pub fn reward_portion(&self, released: u64) -> u64 {
+        if self.winners_paid.saturating_add(1) >= self.winner_count {
+            released.saturating_sub(self.stake_held)
+        } else {
+            released
+        }
+    }
+
+    /// Whether the payout just recorded means its recipient completed the dare:
+    /// every split-bounty winner has, a milestone daree only once the last milestone is paid.
+    pub fn payout_completes_dare(&self) -> bool {
+        !self.is_milestone_dare() || self.status == DareStatus::Completed
+    }
+
+    /// Records a paid winner or milestone. The dare completes once every slot is paid;
+    /// otherwise a bounty keeps reviewing pending submissions, or goes back to Active to take more
+    /// (the next milestone, for a milestone dare).
+    pub fn record_payout(&mut self, now: i64) {
+        self.winners_paid = self.winners_paid.saturating_add(1);
+        if self.winners_paid >= self.winner_count {
+            self.complete(now);
+        } else if self.pending_submissions > 0 {
+            self.status = DareStatus::ProofSubmitted;
+        } else {
+            self.status = DareStatus::Active;
+        }
+    }
+
+    /// Streak dares need one check-in per `streak_period` seconds, for `streak_periods` periods
+    /// ending at the deadline
+    pub fn is_streak(&self) -> bool {
+        self.streak_periods > 0
+    }
+
+    /// When the first check-in period opens
+    pub fn streak_start(&self) -> i64 {
+        self.deadline - self.streak_period * self.streak_periods as i64
+    }
+
+    /// Index of the check-in period `now` falls in, or None outside the streak
+    pub fn streak_period_at(&self, now: i64) -> Option<u8> {
+        if now < self.streak_start() || now >= self.deadline {
+            return None;
+        }
+        Some(((now - self.streak_start()) / self.streak_period) as u8)
+    }
+
+    /// Number of check-in periods that have fully closed by `now`
+    pub fn closed_periods(&self, now: i64) -> u8 {
+        if now < self.streak_start() {
+            return 0;
+        }
+        ((now - self.streak_start()) / self.streak_period).min(self.streak_periods as i64) as u8
+    }
+
+    /// Closed periods past the settled cursor that have no check-in
+    pub fn unsettled_misses(&self, now: i64) -> u8 {
+        (self.periods_settled..self.closed_periods(now))
+            .filter(|&period| self.checkins & (1u64 << period) == 0)
+            .count() as u8
+    }
+
+    /// Advances the settled cursor to `now` and returns how many of the newly settled periods were missed
+    pub fn settle_missed_periods(&mut self, now: i64) -> u8 {
+        let missed = self.unsettled_misses(now);
+        self.periods_settled = self.closed_periods(now);
+        self.missed_periods = self.missed_periods.saturating_add(missed);
+        missed
+    }
+
+    /// Escrow forfeited to the challenger per missed period under MissPolicy::ForfeitSlice
+    pub fn streak_slice(&self) -> u64 {
+        self.amount / self.streak_periods.max(1) as u64
+    }
+
+    /// Marks the dare Completed with no payout slots left.
+    /// The final payout sweeps escrow, so any held stake has been returned by then.
+    pub fn complete(&mut self, now: i64) {
+        self.winners_paid = self.winner_count;
+        self.status = DareStatus::Completed;
+        self.completed_at = now;
+        self.stake_held = 0;
+    }
+
+    /// Pro-rata slice of the refund pool for a contribution of `contribution` to `amount`.
+    pub fn refund_share(&self, contribution: u64) -> Result<u64> {
+        if self.amount == 0 {
+            return Ok(0);
+        }
+        let share = (self.refund_pool as u128)
+            .checked_mul(contribution as u128)
+            .ok_or(DareMeError::ArithmeticOverflow)?
+            / self.amount as u128;
+        u64::try_from(share).map_err(|_| DareMeError::ArithmeticOverflow.into())
+    }
+
+    /// Snapshots what's left in escrow as the refund pool and returns the challenger's share of it.
+    /// Without backers the challenger gets everything; otherwise backers claim their own
+    /// shares later through claim_refund.
+    /// A held counter-stake is forfeited: it goes to the challenger in full and stays out of the pool.
+    pub fn start_refund(&mut self, escrow_balance: u64) -> Result<u64> {
+        let forfeited = self.stake_held.min(escrow_balance);
+        self.stake_held = 0;
+        self.refund_pool = escrow_balance - forfeited;
+        if self.backer_count == 0 {
+            return Ok(escrow_balance);
+        }
+        let challenger_contribution = self.amount.saturating_sub(self.backed_amount);
+        let share = self.refund_share(challenger_contribution)?;
+        share.checked_add(forfeited).ok_or(DareMeError::ArithmeticOverflow.into())
+    }
+
+    /// Whether the dare has reached a final status and nothing more can happen to its escrow
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.status,
+            DareStatus::Completed
+                | DareStatus::Cancelled
+                | DareStatus::Refused
+                | DareStatus::Expired
+                | DareStatus::Failed
+        )
+    }
+
+    /// Whether the dare ended with its escrow (or part of it, after a split ruling) refunded
+    pub fn is_refunded(&self) -> bool {
+        matches!(
+            self.status,
+            DareStatus::Cancelled | DareStatus::Refused | DareStatus::Expired | DareStatus::Failed
+        ) || (self.status == DareStatus::Completed && self.refund_pool > 0)
+    }
+}
+
+/// Program-wide settings (singleton)
+/// Seeds: ["config"]
+#[account]
+pub struct Config {
+    pub admin: Pubkey,                // 32
+    pub treasury: Pubkey,             // 32
+    pub fee_bps: u16,                 // 2
+    pub bump: u8,                     // 1
+    pub guardian: Pubkey,             // 32
+    pub paused: bool,                 // 1
+    pub arbiter: Pubkey,              // 32
+    pub oracle: Pubkey,               // 32
+    pub min_dispute_window: i64,      // 8
+    pub max_dispute_window: i64,      // 8
+}
+
+impl Config {
+    // 8 (discriminator) + 180 fields = 188
+    pub const SPACE: usize = 8 + 32 + 32 + 2 + 1 + 32 + 1 + 32 + 32 + 8 + 8;
+
+    /// The admin can always pause; the guardian is an optional hot key that can only pause/unpause
+    pub fn can_pause(&self, key: &Pubkey) -> bool {
+        *key == self.admin || (self.guardian != Pubkey::default() && *key == self.guardian)
+    }
+
+    /// Protocol fee on a payout of `amount`, rounded down
+    pub fn fee_on(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(DareMeError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        u64::try_from(fee).map_err(|_| DareMeError::ArithmeticOverflow.into())
+    }
 }
 
 /// Per-user reputation stats
 /// Seeds: ["user_stats", user.key()]
-#[account]
+/// `total_earned` / `total_spent` / `judge_fees_earned` are in lamports and only count SOL dares.
+// Versioned: `versioned_account!` below stands in for `#[account]`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct UserStats {
     pub user: Pubkey,                 // 32
     pub dares_created: u32,           // 4
@@ -72,9 +465,228 @@ pub struct UserStats {
     pub total_earned: u64,            // 8
     pub total_spent: u64,             // 8
     pub bump: u8,                     // 1
+    pub milestones_completed: u32,    // 4
+    pub dares_judged: u32,            // 4
+    pub judge_fees_earned: u64,       // 8
+    pub next_dare_id: u64,            // 8
+    pub version: u8,                  // 1
+    /// Score kept by the settlement handlers: completions add to it, failures and ghosting take away
+    pub reputation: i64,              // 8
+    /// Proofs this user left unreviewed until they auto-approved, as challenger or judge
+    pub dares_ghosted: u32,           // 4
+    /// Zeroed space for later versions, as on Dare
+    pub reserved: [u8; 20],           // 20
 }
 
+versioned_account!(UserStats, [176, 223, 136, 27, 122, 79, 32, 227]);
+
 impl UserStats {
-    // 8 (discriminator) + 65 fields = 73
-    pub const SPACE: usize = 8 + 32 + 4 + 4 + 4 + 4 + 8 + 8 + 1;
+    // 8 (discriminator) + 122 fields = 130
+    pub const SPACE: usize = 8 + 32 + 4 + 4 + 4 + 4 + 8 + 8 + 1 + 4 + 4 + 8 + 8 + 1 + 8 + 4 + 20;
+
+    /// Stats that were just created by init_if_needed (no user yet) count as current
+    pub fn is_current(&self) -> bool {
+        self.version == USER_STATS_VERSION || self.user == Pubkey::default()
+    }
+
+    /// The ID a new dare is created under: `requested` if the client picked one,
+    /// or the next one from this user's counter when `requested` is 0
+    pub fn dare_id_for(&self, requested: u64) -> u64 {
+        if requested == 0 {
+            ASSIGNED_DARE_ID_FLAG | self.next_dare_id
+        } else {
+            requested
+        }
+    }
+
+    /// `dare_id_for` on a stats account that hasn't been loaded yet, as the dare's seed in create_dare.
+    /// A stats account that doesn't exist yet has a counter of 0. Returned as bytes so the seed calls it
+    /// directly: IDL builds can't resolve a method chained onto it.
+    pub fn dare_id_seed(info: &AccountInfo, requested: u64) -> [u8; 8] {
+        if requested != 0 {
+            return requested.to_le_bytes();
+        }
+        let next_dare_id = info
+            .try_borrow_data()
+            .ok()
+            .and_then(|data| UserStats::try_deserialize(&mut &data[..]).ok())
+            .map_or(0, |stats| stats.next_dare_id);
+        (ASSIGNED_DARE_ID_FLAG | next_dare_id).to_le_bytes()
+    }
+
+    /// Credits a paid dare or milestone to the daree's reputation. `earned` (in base units of a mint
+    /// with `decimals`) is counted in hundredths of a whole token and earns their square root, plus
+    /// REPUTATION_COMPLETION_POINTS if it completes the dare, scaled down below REPUTATION_FULL_COMPLETION_UNITS.
+    /// The total is weighted by the challenger's reputation when the dare was created (see
+    /// REPUTATION_NEWCOMER_SHARE_BPS) and halves for each dare the two users already completed together,
+    /// so farming it with fresh wallets or between the same few wallets stops paying quickly.
+    /// A completion is then added to the pair's dealings.
+    pub fn credit_reputation(
+        &mut self,
+        dare: &Dare,
+        earned: u64,
+        decimals: u8,
+        completes: bool,
+        dealings: &mut Dealings,
+    ) {
+        let units = 10u128
+            .checked_pow(decimals as u32)
+            .map_or(0, |one| earned as u128 * REPUTATION_UNITS_PER_TOKEN as u128 / one);
+        let mut points = units.isqrt() as i64;
+        if completes {
+            points += REPUTATION_COMPLETION_POINTS * units.min(REPUTATION_FULL_COMPLETION_UNITS as u128) as i64
+                / REPUTATION_FULL_COMPLETION_UNITS as i64;
+        }
+        let standing = dare.challenger_reputation.clamp(0, REPUTATION_TRUSTED_CHALLENGER) as u64;
+        let share_bps = REPUTATION_NEWCOMER_SHARE_BPS
+            + (BPS_DENOMINATOR - REPUTATION_NEWCOMER_SHARE_BPS) * standing / REPUTATION_TRUSTED_CHALLENGER as u64;
+        points = points * share_bps as i64 / BPS_DENOMINATOR as i64;
+        points >>= dealings.completions.min(63);
+        self.reputation = self.reputation.saturating_add(points);
+        if completes {
+            dealings.completions = dealings.completions.saturating_add(1);
+        }
+    }
+
+    /// Counts a failed dare against the daree
+    pub fn record_failure(&mut self) {
+        self.dares_failed += 1;
+        self.reputation = self.reputation.saturating_sub(REPUTATION_FAILURE_PENALTY);
+    }
+
+    /// Counts a proof this user should have reviewed but let auto-approve
+    pub fn record_ghosting(&mut self) {
+        self.dares_ghosted += 1;
+        self.reputation = self.reputation.saturating_sub(REPUTATION_GHOSTING_PENALTY);
+    }
+
+    /// Whether the user clears a dare's reputation gate; a gate of 0 lets anyone through
+    pub fn meets_reputation(&self, min_reputation: i64) -> bool {
+        min_reputation == 0 || self.reputation >= min_reputation
+    }
+}
+
+/// Dares two users have completed with each other, in either role, used to discount repeat reputation
+/// Seeds: ["dealings", lower pubkey, higher pubkey] (see Dealings::lower / Dealings::higher)
+#[account]
+pub struct Dealings {
+    pub user_a: Pubkey,               // 32
+    pub user_b: Pubkey,               // 32
+    pub completions: u32,             // 4
+    pub bump: u8,                     // 1
+}
+
+impl Dealings {
+    // 8 (discriminator) + 69 fields = 77
+    pub const SPACE: usize = 8 + 32 + 32 + 4 + 1;
+
+    /// First seed of the pair's account, so either user can be passed first.
+    /// Returned as bytes so seeds call it directly: IDL builds can't resolve a method chained onto it.
+    pub fn lower<'a>(a: &'a Pubkey, b: &'a Pubkey) -> &'a [u8] {
+        a.min(b).as_ref()
+    }
+
+    /// Second seed of the pair's account
+    pub fn higher<'a>(a: &'a Pubkey, b: &'a Pubkey) -> &'a [u8] {
+        a.max(b).as_ref()
+    }
+
+    /// Fills in a freshly created account (init_if_needed); no-op once it exists
+    pub fn open(&mut self, a: &Pubkey, b: &Pubkey, bump: u8) {
+        if self.user_a == Pubkey::default() {
+            self.user_a = *a.min(b);
+            self.user_b = *a.max(b);
+            self.bump = bump;
+        }
+    }
+}
+
+/// One submitter's proof for a PublicBounty dare
+/// Seeds: ["submission", dare.key(), submitter.key()]
+#[account]
+pub struct Submission {
+    pub dare: Pubkey,                 // 32
+    pub submitter: Pubkey,            // 32
+    pub proof_hash: [u8; 32],         // 32
+    pub submitted_at: i64,            // 8
+    pub status: SubmissionStatus,     // 1
+    pub bump: u8,                     // 1
+    pub attempt: u32,                 // 4
+}
+
+impl Submission {
+    // 8 (discriminator) + 110 fields = 118
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 1 + 1 + 4;
+}
+
+/// One proof submitted to a dare, kept after rejection so earlier attempts stay on record
+/// Seeds: ["proof_attempt", dare.key(), index (u32 LE)]
+#[account]
+pub struct ProofAttempt {
+    pub dare: Pubkey,                 // 32
+    pub submitter: Pubkey,            // 32
+    pub index: u32,                   // 4
+    pub proof_hash: [u8; 32],         // 32
+    pub submitted_at: i64,            // 8
+    pub rejected_at: i64,             // 8
+    pub has_rejection_reason: bool,   // 1
+    pub rejection_reason_hash: [u8; 32], // 32
+    pub bump: u8,                     // 1
+}
+
+impl ProofAttempt {
+    // 8 (discriminator) + 150 fields = 158
+    pub const SPACE: usize = 8 + 32 + 32 + 4 + 32 + 8 + 8 + 1 + 32 + 1;
+
+    /// Marks the attempt rejected; an all-zero reason_hash means no reason was given
+    pub fn reject(&mut self, now: i64, reason_hash: [u8; 32]) {
+        self.rejected_at = now;
+        self.has_rejection_reason = reason_hash != [0u8; 32];
+        self.rejection_reason_hash = reason_hash;
+    }
+}
+
+/// Marks a mint with a freeze authority as accepted for escrow (admin-managed allowlist)
+/// Seeds: ["allowed_mint", mint.key()]
+#[account]
+pub struct AllowedMint {
+    pub mint: Pubkey,                 // 32
+    pub bump: u8,                     // 1
+}
+
+impl AllowedMint {
+    // 8 (discriminator) + 33 fields = 41
+    pub const SPACE: usize = 8 + 32 + 1;
+}
+
+/// A third-party contribution to a dare's escrow (one per backer per dare)
+/// Seeds: ["backer", dare.key(), backer.key()]
+#[account]
+pub struct Backer {
+    pub dare: Pubkey,                 // 32
+    pub backer: Pubkey,               // 32
+    pub amount: u64,                  // 8
+    pub bump: u8,                     // 1
+    pub backed_at: i64,               // 8 (last contribution; older than the dare means it outlived a closed dare)
+}
+
+impl Backer {
+    // 8 (discriminator) + 81 fields = 89
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1 + 8;
+}
+
+/// A single community vote on a CommunityVote dare's proof (one per wallet per dare)
+/// Seeds: ["vote", dare.key(), voter.key()]
+#[account]
+pub struct Vote {
+    pub dare: Pubkey,                 // 32
+    pub voter: Pubkey,                // 32
+    pub approve: bool,                // 1
+    pub voted_at: i64,                // 8
+    pub bump: u8,                     // 1
+}
+
+impl Vote {
+    // 8 (discriminator) + 74 fields = 82
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 1;
 }
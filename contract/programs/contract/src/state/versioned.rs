@@ -0,0 +1,56 @@
+/// Implements what `#[account]` would for a versioned account, except that an account shorter than
+/// the current layout fails to deserialize with AccountNotMigrated. Version 0 accounts are shorter,
+/// so without this they would fail with AccountDidNotDeserialize before any `is_current()` constraint ran.
+/// The discriminator has to stay sha256("account:<Name>")[..8] so existing accounts keep matching.
+macro_rules! versioned_account {
+    ($name:ident, $discriminator:expr) => {
+        #[automatically_derived]
+        impl anchor_lang::Discriminator for $name {
+            const DISCRIMINATOR: &'static [u8] = &$discriminator;
+        }
+
+        #[automatically_derived]
+        impl anchor_lang::Owner for $name {
+            fn owner() -> Pubkey {
+                crate::ID
+            }
+        }
+
+        #[automatically_derived]
+        impl anchor_lang::AccountSerialize for $name {
+            fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+                if writer.write_all(<$name as anchor_lang::Discriminator>::DISCRIMINATOR).is_err() {
+                    return Err(anchor_lang::error::ErrorCode::AccountDidNotSerialize.into());
+                }
+                if AnchorSerialize::serialize(self, writer).is_err() {
+                    return Err(anchor_lang::error::ErrorCode::AccountDidNotSerialize.into());
+                }
+                Ok(())
+            }
+        }
+
+        #[automatically_derived]
+        impl anchor_lang::AccountDeserialize for $name {
+            fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+                let discriminator = <$name as anchor_lang::Discriminator>::DISCRIMINATOR;
+                if buf.len() < discriminator.len() {
+                    return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound.into());
+                }
+                if discriminator != &buf[..discriminator.len()] {
+                    return Err(error!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch)
+                        .with_account_name(stringify!($name)));
+                }
+                require!(buf.len() >= $name::SPACE, crate::error::DareMeError::AccountNotMigrated);
+                Self::try_deserialize_unchecked(buf)
+            }
+
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+                let discriminator = <$name as anchor_lang::Discriminator>::DISCRIMINATOR;
+                let mut data: &[u8] = &buf[discriminator.len()..];
+                AnchorDeserialize::deserialize(&mut data)
+                    .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+            }
+        }
+    };
+}
+pub(crate) use versioned_account;
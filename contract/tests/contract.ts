@@ -1,14 +1,34 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { IdlTypes, Program } from "@coral-xyz/anchor";
 import { Contract } from "../target/types/contract";
 import { expect } from "chai";
 import {
+  Ed25519Program,
   Keypair,
   LAMPORTS_PER_SOL,
   PublicKey,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+  sendAndConfirmTransaction,
 } from "@solana/web3.js";
+import {
+  createMint,
+  createInitializeMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  ExtensionType,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  getAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import BN from "bn.js";
+import { createHash } from "crypto";
 
 // ============================================================================
 // Helpers
@@ -17,6 +37,14 @@ import BN from "bn.js";
 const DARE_SEED = Buffer.from("dare");
 const VAULT_SEED = Buffer.from("vault");
 const USER_STATS_SEED = Buffer.from("user_stats");
+const VOTE_SEED = Buffer.from("vote");
+const SUBMISSION_SEED = Buffer.from("submission");
+const BACKER_SEED = Buffer.from("backer");
+const CONFIG_SEED = Buffer.from("config");
+const PROOF_ATTEMPT_SEED = Buffer.from("proof_attempt");
+const DEALINGS_SEED = Buffer.from("dealings");
+const ALLOWED_MINT_SEED = Buffer.from("allowed_mint");
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
 
 function getDarePDA(
   programId: PublicKey,
@@ -49,11 +77,127 @@ function getUserStatsPDA(
   );
 }
 
+function getVotePDA(
+  programId: PublicKey,
+  dareKey: PublicKey,
+  voter: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [VOTE_SEED, dareKey.toBuffer(), voter.toBuffer()],
+    programId
+  );
+}
+
+function getSubmissionPDA(
+  programId: PublicKey,
+  dareKey: PublicKey,
+  submitter: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [SUBMISSION_SEED, dareKey.toBuffer(), submitter.toBuffer()],
+    programId
+  );
+}
+
+function getBackerPDA(
+  programId: PublicKey,
+  dareKey: PublicKey,
+  backer: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [BACKER_SEED, dareKey.toBuffer(), backer.toBuffer()],
+    programId
+  );
+}
+
+function getProofAttemptPDA(
+  programId: PublicKey,
+  dareKey: PublicKey,
+  index: number
+): [PublicKey, number] {
+  const indexBuf = Buffer.alloc(4);
+  indexBuf.writeUInt32LE(index);
+  return PublicKey.findProgramAddressSync(
+    [PROOF_ATTEMPT_SEED, dareKey.toBuffer(), indexBuf],
+    programId
+  );
+}
+
+function getDealingsPDA(
+  programId: PublicKey,
+  a: PublicKey,
+  b: PublicKey
+): [PublicKey, number] {
+  const [lower, higher] = Buffer.compare(a.toBuffer(), b.toBuffer()) <= 0 ? [a, b] : [b, a];
+  return PublicKey.findProgramAddressSync(
+    [DEALINGS_SEED, lower.toBuffer(), higher.toBuffer()],
+    programId
+  );
+}
+
+function getConfigPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([CONFIG_SEED], programId);
+}
+
 /** Returns a unix timestamp N seconds from now */
 function futureTimestamp(seconds: number): BN {
   return new BN(Math.floor(Date.now() / 1000) + seconds);
 }
 
+/** Ed25519 program instruction carrying `signer`'s attestation over (dare, created_at, proof hash, attempt, winners paid, verdict) */
+function attestationIx(
+  signer: Keypair,
+  dare: PublicKey,
+  createdAt: BN,
+  proofHash: number[],
+  attempt: number,
+  winnersPaid: number,
+  approve: boolean,
+) {
+  const attemptLe = Buffer.alloc(4);
+  attemptLe.writeUInt32LE(attempt);
+  const message = Buffer.concat([
+    dare.toBuffer(),
+    createdAt.toArrayLike(Buffer, "le", 8),
+    Buffer.from(proofHash),
+    attemptLe,
+    Buffer.from([winnersPaid, approve ? 1 : 0]),
+  ]);
+  return Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message });
+}
+
+/** Waits for the validator clock to move past short streak periods */
+function sleep(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+/** Decodes the Anchor events a confirmed transaction emitted through self-CPI */
+async function cpiEvents(program: Program<Contract>, signature: string) {
+  const tx = await program.provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const events = [];
+  for (const inner of tx?.meta?.innerInstructions ?? []) {
+    for (const ix of inner.instructions) {
+      const data = anchor.utils.bytes.bs58.decode(ix.data);
+      const event = program.coder.events.decode(anchor.utils.bytes.base64.encode(data.subarray(8)));
+      if (event) events.push(event);
+    }
+  }
+  return events;
+}
+
+/** Reads the u64 an instruction returned via return data (e.g. the ID create_dare assigned) */
+async function returnedU64(connection: anchor.web3.Connection, signature: string): Promise<BN> {
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const [data] = tx.meta.returnData.data;
+  return new BN(Buffer.from(data, "base64"), "le");
+}
+
 /** Airdrops SOL to a keypair and confirms */
 async function airdrop(
   connection: anchor.web3.Connection,
@@ -64,11 +208,70 @@ async function airdrop(
   await connection.confirmTransaction(sig, "confirmed");
 }
 
+/** Creates a Token-2022 mint (6 decimals) with a single extension initialized */
+async function createToken2022Mint(
+  connection: anchor.web3.Connection,
+  payer: Keypair,
+  extension: ExtensionType.TransferFeeConfig | ExtensionType.PermanentDelegate
+): Promise<PublicKey> {
+  const mintKeypair = Keypair.generate();
+  const mintLen = getMintLen([extension]);
+  const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+  const initExtension =
+    extension === ExtensionType.TransferFeeConfig
+      ? createInitializeTransferFeeConfigInstruction(
+          mintKeypair.publicKey, payer.publicKey, payer.publicKey,
+          100, BigInt(1_000_000_000), TOKEN_2022_PROGRAM_ID // 1% fee
+        )
+      : createInitializePermanentDelegateInstruction(
+          mintKeypair.publicKey, payer.publicKey, TOKEN_2022_PROGRAM_ID
+        );
+
+  const tx = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: payer.publicKey,
+      newAccountPubkey: mintKeypair.publicKey,
+      space: mintLen,
+      lamports,
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    initExtension,
+    createInitializeMintInstruction(mintKeypair.publicKey, 6, payer.publicKey, null, TOKEN_2022_PROGRAM_ID)
+  );
+  await sendAndConfirmTransaction(connection, tx, [payer, mintKeypair]);
+  return mintKeypair.publicKey;
+}
+
 /** A fake 32-byte hash for testing */
 function fakeHash(seed: number = 1): number[] {
   return Array(32).fill(seed);
 }
 
+/** create_dare's optional settings (CreateDareParams), all off unless overridden */
+type DareParams = IdlTypes<Contract>["createDareParams"];
+function dareParams(overrides: Partial<DareParams> = {}): DareParams {
+  return {
+    targetDaree: PublicKey.default,
+    payoutSharesBps: [],
+    dareeStake: new BN(0),
+    streakPeriod: new BN(0),
+    streakPeriods: 0,
+    missPolicy: { endDare: {} },
+    judge: PublicKey.default,
+    judgeFeeBps: 0,
+    disputeWindow: new BN(0),
+    timeToComplete: new BN(0),
+    minReputation: new BN(0),
+    ...overrides,
+  };
+}
+
+/** SHA-256 of the UTF-8 text, as clients compute description_hash */
+function descriptionHash(text: string): number[] {
+  return Array.from(createHash("sha256").update(text, "utf8").digest());
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -87,6 +290,34 @@ describe("DareMe Contract", () => {
   // Shared state for sequential tests
   let dareIdCounter = 0;
 
+  // Global config: the provider wallet is admin, fees go to a dedicated treasury (0% unless a test raises it)
+  const treasury = Keypair.generate();
+  const [configPDA] = getConfigPDA(program.programId);
+
+  before(async () => {
+    await airdrop(connection, treasury.publicKey);
+
+    // Only the upgrade authority (the deploying wallet) may claim the admin seat
+    const [programDataPDA] = PublicKey.findProgramAddressSync([program.programId.toBuffer()], BPF_LOADER_UPGRADEABLE_ID);
+    const squatter = Keypair.generate();
+    await airdrop(connection, squatter.publicKey);
+    try {
+      await program.methods
+        .initializeConfig(squatter.publicKey, 0)
+        .accounts({ admin: squatter.publicKey, config: configPDA, programData: programDataPDA, systemProgram: SystemProgram.programId })
+        .signers([squatter])
+        .rpc();
+      expect.fail("Should have thrown UnauthorizedAdmin");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+    }
+
+    await program.methods
+      .initializeConfig(treasury.publicKey, 0)
+      .accounts({ admin: provider.wallet.publicKey, config: configPDA, programData: programDataPDA, systemProgram: SystemProgram.programId })
+      .rpc();
+  });
+
   beforeEach(async () => {
     challenger = Keypair.generate();
     daree = Keypair.generate();
@@ -99,6 +330,114 @@ describe("DareMe Contract", () => {
     ]);
   });
 
+  // Program-assigned dare IDs have the top bit set
+  const ASSIGNED_FLAG = new BN(1).shln(63);
+
+  type DareStage = "created" | "active" | "submitted" | "rejected" | "disputed" | "approved";
+
+  /**
+   * Creates a dare and walks it to `stage`: accepted, proof submitted (hash `seed + 1`),
+   * rejected by the challenger (reason `seed + 2`) and disputed, or approved.
+   * Defaults to a 1 SOL DirectDare from `challenger` to `daree`, due in 24h, with no optional settings.
+   * A `dareId` of 0 lets the program assign the next ID. `sig` is the last step's signature.
+   */
+  async function setupDare(
+    stage: DareStage,
+    seed: number,
+    opts: {
+      dareId?: BN;
+      descriptionHash?: number[];
+      amount?: number;
+      deadline?: BN;
+      dareType?: any;
+      winnerSelection?: any;
+      params?: Partial<DareParams>;
+      from?: Keypair;
+      to?: Keypair;
+    } = {},
+  ) {
+    const from = opts.from ?? challenger;
+    const to = opts.to ?? daree;
+    const [challengerStatsPDA] = getUserStatsPDA(program.programId, from.publicKey);
+    const [dareeStatsPDA] = getUserStatsPDA(program.programId, to.publicKey);
+
+    let dareId = opts.dareId ?? new BN(++dareIdCounter);
+    if (dareId.isZero()) {
+      const stats = await program.account.userStats.fetchNullable(challengerStatsPDA);
+      dareId = ASSIGNED_FLAG.or(stats ? stats.nextDareId : new BN(0));
+    }
+    const [darePDA] = getDarePDA(program.programId, from.publicKey, dareId);
+    const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+    const [proofAttemptPDA] = getProofAttemptPDA(program.programId, darePDA, 0);
+    const proofHash = fakeHash(seed + 1);
+
+    // Only the last step waits for confirmation, so its signature can be looked up
+    const rpcOptions = (step: DareStage) => (step === stage ? { commitment: "confirmed" as const } : {});
+
+    let sig = await program.methods
+      .createDare(
+        opts.dareId ?? dareId,
+        opts.descriptionHash ?? fakeHash(seed),
+        new BN(opts.amount ?? LAMPORTS_PER_SOL),
+        opts.deadline ?? futureTimestamp(86400),
+        opts.dareType ?? { directDare: {} },
+        opts.winnerSelection ?? { challengerSelect: {} },
+        dareParams(opts.params)
+      )
+      .accounts({
+        challenger: from.publicKey, dare: darePDA, vault: vaultPDA,
+        challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+      })
+      .signers([from])
+      .rpc(rpcOptions("created"));
+
+    if (stage !== "created") {
+      sig = await program.methods.acceptDare()
+        .accounts({ daree: to.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([to])
+        .rpc(rpcOptions("active"));
+    }
+
+    if (stage !== "created" && stage !== "active") {
+      sig = await program.methods.submitProof(proofHash)
+        .accounts({
+          submitter: to.publicKey, dare: darePDA, submitterStats: dareeStatsPDA,
+          proofAttempt: proofAttemptPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([to])
+        .rpc(rpcOptions("submitted"));
+    }
+
+    if (stage === "approved") {
+      sig = await program.methods.approveDare()
+        .accounts({
+          authority: from.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: to.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, from.publicKey, to.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([from])
+        .rpc(rpcOptions("approved"));
+    }
+
+    if (stage === "rejected" || stage === "disputed") {
+      sig = await program.methods.rejectDare(fakeHash(seed + 2))
+        .accounts({ authority: from.publicKey, dare: darePDA, proofAttempt: proofAttemptPDA })
+        .signers([from])
+        .rpc(rpcOptions("rejected"));
+    }
+
+    if (stage === "disputed") {
+      sig = await program.methods.disputeRejection()
+        .accounts({ daree: to.publicKey, dare: darePDA, config: configPDA })
+        .signers([to])
+        .rpc(rpcOptions("disputed"));
+    }
+
+    const { createdAt } = await program.account.dare.fetch(darePDA);
+    return { dareId, darePDA, vaultPDA, challengerStatsPDA, dareeStatsPDA, proofAttemptPDA, proofHash, createdAt, sig };
+  }
+
   // --------------------------------------------------------------------------
   // create_dare
   // --------------------------------------------------------------------------
@@ -121,7 +460,7 @@ describe("DareMe Contract", () => {
           deadline,
           { directDare: {} },
           { challengerSelect: {} },
-          PublicKey.default  // no target
+          dareParams()
         )
         .accounts({
           challenger: challenger.publicKey,
@@ -170,7 +509,7 @@ describe("DareMe Contract", () => {
           deadline,
           { directDare: {} },
           { challengerSelect: {} },
-          daree.publicKey  // targeted daree
+          dareParams({ targetDaree: daree.publicKey })
         )
         .accounts({
           challenger: challenger.publicKey,
@@ -205,7 +544,7 @@ describe("DareMe Contract", () => {
           deadline,
           { publicBounty: {} },
           { challengerSelect: {} },
-          PublicKey.default
+          dareParams()
         )
         .accounts({
           challenger: challenger.publicKey,
@@ -237,7 +576,7 @@ describe("DareMe Contract", () => {
             futureTimestamp(86400),
             { directDare: {} },
             { challengerSelect: {} },
-            PublicKey.default
+            dareParams()
           )
           .accounts({
             challenger: challenger.publicKey,
@@ -269,7 +608,7 @@ describe("DareMe Contract", () => {
             futureTimestamp(31 * 24 * 60 * 60), // 31 days
             { directDare: {} },
             { challengerSelect: {} },
-            PublicKey.default
+            dareParams()
           )
           .accounts({
             challenger: challenger.publicKey,
@@ -301,7 +640,7 @@ describe("DareMe Contract", () => {
             futureTimestamp(86400),
             { directDare: {} },
             { challengerSelect: {} },
-            challenger.publicKey  // target yourself
+            dareParams({ targetDaree: challenger.publicKey })
           )
           .accounts({
             challenger: challenger.publicKey,
@@ -336,7 +675,7 @@ describe("DareMe Contract", () => {
 
       // Create an open DirectDare
       await program.methods
-        .createDare(dareId, fakeHash(10), amount, deadline, { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(10), amount, deadline, { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -377,7 +716,7 @@ describe("DareMe Contract", () => {
 
       // Create a targeted DirectDare
       await program.methods
-        .createDare(dareId, fakeHash(10), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, daree.publicKey)
+        .createDare(dareId, fakeHash(10), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ targetDaree: daree.publicKey }))
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -413,7 +752,7 @@ describe("DareMe Contract", () => {
 
       // Create targeted at daree
       await program.methods
-        .createDare(dareId, fakeHash(10), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, daree.publicKey)
+        .createDare(dareId, fakeHash(10), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ targetDaree: daree.publicKey }))
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -449,7 +788,7 @@ describe("DareMe Contract", () => {
       const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(11), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(11), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -485,7 +824,7 @@ describe("DareMe Contract", () => {
       const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(12), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(12), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -528,7 +867,7 @@ describe("DareMe Contract", () => {
 
       // Create a targeted DirectDare
       await program.methods
-        .createDare(dareId, fakeHash(80), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, daree.publicKey)
+        .createDare(dareId, fakeHash(80), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ targetDaree: daree.publicKey }))
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -583,7 +922,7 @@ describe("DareMe Contract", () => {
       const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(81), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, daree.publicKey)
+        .createDare(dareId, fakeHash(81), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ targetDaree: daree.publicKey }))
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -622,7 +961,7 @@ describe("DareMe Contract", () => {
 
       // Create an open DirectDare (no target)
       await program.methods
-        .createDare(dareId, fakeHash(82), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(82), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -659,7 +998,7 @@ describe("DareMe Contract", () => {
       const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(83), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(83), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey,
           dare: darePDA,
@@ -704,7 +1043,7 @@ describe("DareMe Contract", () => {
 
       // Create + Accept
       await program.methods
-        .createDare(dareId, fakeHash(20), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(20), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -746,7 +1085,7 @@ describe("DareMe Contract", () => {
       const [outsiderStatsPDA] = getUserStatsPDA(program.programId, outsider.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(21), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(21), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -754,20 +1093,28 @@ describe("DareMe Contract", () => {
         .signers([challenger])
         .rpc();
 
+      const [submissionPDA] = getSubmissionPDA(program.programId, darePDA, outsider.publicKey);
+
       // Outsider submits proof directly (no accept step needed)
       await program.methods
         .submitProof(fakeHash(43))
         .accounts({
           submitter: outsider.publicKey, dare: darePDA, submitterStats: outsiderStatsPDA,
-          systemProgram: SystemProgram.programId,
+          submission: submissionPDA, systemProgram: SystemProgram.programId,
         })
         .signers([outsider])
         .rpc();
 
       const dareAccount = await program.account.dare.fetch(darePDA);
       expect(dareAccount.status).to.deep.equal({ proofSubmitted: {} });
-      expect(dareAccount.hasDaree).to.be.true;
-      expect(dareAccount.daree.toBase58()).to.equal(outsider.publicKey.toBase58());
+      expect(dareAccount.pendingSubmissions).to.equal(1);
+      // The daree is only set once a submission wins
+      expect(dareAccount.hasDaree).to.be.false;
+
+      const submission = await program.account.submission.fetch(submissionPDA);
+      expect(submission.submitter.toBase58()).to.equal(outsider.publicKey.toBase58());
+      expect(submission.proofHash).to.deep.equal(fakeHash(43));
+      expect(submission.status).to.deep.equal({ pending: {} });
 
       // Check dares_accepted was incremented for PublicBounty
       const stats = await program.account.userStats.fetch(outsiderStatsPDA);
@@ -783,7 +1130,7 @@ describe("DareMe Contract", () => {
       const [outsiderStatsPDA] = getUserStatsPDA(program.programId, outsider.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(22), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(22), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -831,7 +1178,7 @@ describe("DareMe Contract", () => {
 
       // Create → Accept → Submit Proof
       await program.methods
-        .createDare(dareId, fakeHash(30), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(30), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -861,8 +1208,9 @@ describe("DareMe Contract", () => {
       // Approve
       await program.methods.approveDare()
         .accounts({
-          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
           systemProgram: SystemProgram.programId,
         })
         .signers([challenger])
@@ -896,7 +1244,7 @@ describe("DareMe Contract", () => {
       const [outsiderStatsPDA] = getUserStatsPDA(program.programId, outsider.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(31), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(31), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -918,16 +1266,17 @@ describe("DareMe Contract", () => {
         // Outsider tries to approve
         await program.methods.approveDare()
           .accounts({
-            challenger: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+            authority: outsider.publicKey, dare: darePDA, vault: vaultPDA,
             daree: daree.publicKey, dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
             systemProgram: SystemProgram.programId,
           })
           .signers([outsider])
           .rpc();
         expect.fail("Should have thrown");
       } catch (err: any) {
-        // has_one constraint will fail
-        expect(err).to.exist;
+        // Without a judge only the challenger can approve
+        expect(err.error.errorCode.code).to.equal("UnauthorizedJudge");
       }
     });
   });
@@ -946,7 +1295,7 @@ describe("DareMe Contract", () => {
 
       // Create → Accept → Submit Proof
       await program.methods
-        .createDare(dareId, fakeHash(40), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(40), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -965,8 +1314,8 @@ describe("DareMe Contract", () => {
         .rpc();
 
       // Reject proof
-      await program.methods.rejectDare()
-        .accounts({ challenger: challenger.publicKey, dare: darePDA })
+      await program.methods.rejectDare(fakeHash(0))
+        .accounts({ authority: challenger.publicKey, dare: darePDA, proofAttempt: getProofAttemptPDA(program.programId, darePDA, 0)[0] })
         .signers([challenger])
         .rpc();
 
@@ -1001,7 +1350,7 @@ describe("DareMe Contract", () => {
       const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(50), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(50), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: statsPDA, systemProgram: SystemProgram.programId,
@@ -1041,7 +1390,7 @@ describe("DareMe Contract", () => {
       const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
 
       await program.methods
-        .createDare(dareId, fakeHash(51), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(51), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -1086,7 +1435,7 @@ describe("DareMe Contract", () => {
 
       // 1. Create
       await program.methods
-        .createDare(dareId, fakeHash(60), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(60), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -1109,8 +1458,9 @@ describe("DareMe Contract", () => {
       // 4. Approve
       await program.methods.approveDare()
         .accounts({
-          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
           systemProgram: SystemProgram.programId,
         })
         .signers([challenger])
@@ -1141,7 +1491,7 @@ describe("DareMe Contract", () => {
 
       // 1. Create PublicBounty
       await program.methods
-        .createDare(dareId, fakeHash(70), amount, futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, PublicKey.default)
+        .createDare(dareId, fakeHash(70), amount, futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams())
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -1149,18 +1499,21 @@ describe("DareMe Contract", () => {
         .signers([challenger])
         .rpc();
 
+      const [submissionPDA] = getSubmissionPDA(program.programId, darePDA, outsider.publicKey);
+
       // 2. Outsider submits proof directly (skips accept)
       await program.methods.submitProof(fakeHash(71))
-        .accounts({ submitter: outsider.publicKey, dare: darePDA, submitterStats: outsiderStatsPDA, systemProgram: SystemProgram.programId })
+        .accounts({ submitter: outsider.publicKey, dare: darePDA, submitterStats: outsiderStatsPDA, submission: submissionPDA, systemProgram: SystemProgram.programId })
         .signers([outsider])
         .rpc();
 
-      // 3. Challenger approves
+      // 3. Challenger approves the submission
       await program.methods.approveDare()
         .accounts({
-          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           daree: outsider.publicKey, dareeStats: outsiderStatsPDA,
-          systemProgram: SystemProgram.programId,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, outsider.publicKey)[0],
+          submission: submissionPDA, systemProgram: SystemProgram.programId,
         })
         .signers([challenger])
         .rpc();
@@ -1181,7 +1534,7 @@ describe("DareMe Contract", () => {
 
       // 1. Create targeted dare
       await program.methods
-        .createDare(dareId, fakeHash(90), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, daree.publicKey)
+        .createDare(dareId, fakeHash(90), amount, futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ targetDaree: daree.publicKey }))
         .accounts({
           challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
           challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
@@ -1210,4 +1563,2966 @@ describe("DareMe Contract", () => {
       expect(stats.totalSpent.toNumber()).to.equal(0);
     });
   });
+
+  // --------------------------------------------------------------------------
+  // SPL token dares
+  // --------------------------------------------------------------------------
+
+  describe("token dares", () => {
+    const TOKEN_AMOUNT = 25_000_000; // 25 USDC (6 decimals)
+    let mint: PublicKey;
+    let challengerAta: PublicKey;
+
+    beforeEach(async () => {
+      mint = await createMint(connection, challenger, challenger.publicKey, null, 6);
+      challengerAta = (
+        await getOrCreateAssociatedTokenAccount(connection, challenger, mint, challenger.publicKey)
+      ).address;
+      await mintTo(connection, challenger, mint, challengerAta, challenger, 100_000_000);
+    });
+
+    it("escrows tokens and releases them to the daree on approval", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const vaultAta = getAssociatedTokenAddressSync(mint, vaultPDA, true);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+      const dareeAta = (
+        await getOrCreateAssociatedTokenAccount(connection, daree, mint, daree.publicKey)
+      ).address;
+
+      await program.methods
+        .createDare(dareId, fakeHash(100), new BN(TOKEN_AMOUNT), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, mint,
+          challengerTokenAccount: challengerAta, vaultTokenAccount: vaultAta,
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      let dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.hasMint).to.be.true;
+      expect(dareAccount.mint.toBase58()).to.equal(mint.toBase58());
+      expect(Number((await getAccount(connection, vaultAta)).amount)).to.equal(TOKEN_AMOUNT);
+
+      // Token amounts are not counted in lamport totals
+      const challengerStats = await program.account.userStats.fetch(challengerStatsPDA);
+      expect(challengerStats.totalSpent.toNumber()).to.equal(0);
+
+      await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      await program.methods.submitProof(fakeHash(101))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      await program.methods.approveDare()
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA, mint,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          vaultTokenAccount: vaultAta, dareeTokenAccount: dareeAta,
+          tokenProgram: TOKEN_PROGRAM_ID, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ completed: {} });
+      expect(Number((await getAccount(connection, dareeAta)).amount)).to.equal(TOKEN_AMOUNT);
+      expect(Number((await getAccount(connection, vaultAta)).amount)).to.equal(0);
+
+      // Reputation counts the 25 tokens through the mint's decimals: sqrt(2500) + 10 completion points,
+      // a quarter of it from a challenger with no reputation yet
+      const dareeStats = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(dareeStats.totalEarned.toNumber()).to.equal(0);
+      expect(dareeStats.reputation.toNumber()).to.equal(15);
+    });
+
+    it("refunds tokens to the challenger on cancel", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const vaultAta = getAssociatedTokenAddressSync(mint, vaultPDA, true);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(102), new BN(TOKEN_AMOUNT), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, mint,
+          challengerTokenAccount: challengerAta, vaultTokenAccount: vaultAta,
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, mint,
+          vaultTokenAccount: vaultAta, challengerTokenAccount: challengerAta,
+          tokenProgram: TOKEN_PROGRAM_ID, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ cancelled: {} });
+      expect(Number((await getAccount(connection, challengerAta)).amount)).to.equal(100_000_000);
+    });
+
+    it("rejects a token dare settled without token accounts", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const vaultAta = getAssociatedTokenAddressSync(mint, vaultPDA, true);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(103), new BN(TOKEN_AMOUNT), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, mint,
+          challengerTokenAccount: challengerAta, vaultTokenAccount: vaultAta,
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      try {
+        await program.methods.cancelDare()
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown MissingTokenAccounts");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MissingTokenAccounts");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // Token-2022 dares
+  // --------------------------------------------------------------------------
+
+  describe("token-2022 dares", () => {
+    it("records the post-fee amount for a transfer-fee mint", async () => {
+      const mint = await createToken2022Mint(connection, challenger, ExtensionType.TransferFeeConfig);
+      const challengerAta = (
+        await getOrCreateAssociatedTokenAccount(connection, challenger, mint, challenger.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID)
+      ).address;
+      await mintTo(connection, challenger, mint, challengerAta, challenger, 100_000_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const vaultAta = getAssociatedTokenAddressSync(mint, vaultPDA, true, TOKEN_2022_PROGRAM_ID);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(110), new BN(10_000_000), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, mint,
+          challengerTokenAccount: challengerAta, vaultTokenAccount: vaultAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      // 1% of 10 tokens is withheld by the mint
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.amount.toNumber()).to.equal(9_900_000);
+      const vault = await getAccount(connection, vaultAta, undefined, TOKEN_2022_PROGRAM_ID);
+      expect(Number(vault.amount)).to.equal(9_900_000);
+    });
+
+    it("rejects a mint with a permanent delegate", async () => {
+      const mint = await createToken2022Mint(connection, challenger, ExtensionType.PermanentDelegate);
+      const challengerAta = (
+        await getOrCreateAssociatedTokenAccount(connection, challenger, mint, challenger.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID)
+      ).address;
+      await mintTo(connection, challenger, mint, challengerAta, challenger, 100_000_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const vaultAta = getAssociatedTokenAddressSync(mint, vaultPDA, true, TOKEN_2022_PROGRAM_ID);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      try {
+        await program.methods
+          .createDare(dareId, fakeHash(111), new BN(10_000_000), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: statsPDA, mint,
+            challengerTokenAccount: challengerAta, vaultTokenAccount: vaultAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown UnsupportedMintExtension");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnsupportedMintExtension");
+      }
+    });
+
+    it("only escrows a mint with a freeze authority once the admin allowlists it", async () => {
+      const freezable = await createMint(connection, challenger, challenger.publicKey, challenger.publicKey, 6);
+      const ata = (
+        await getOrCreateAssociatedTokenAccount(connection, challenger, freezable, challenger.publicKey)
+      ).address;
+      await mintTo(connection, challenger, freezable, ata, challenger, 100_000_000);
+      const [allowedMintPDA] = PublicKey.findProgramAddressSync([ALLOWED_MINT_SEED, freezable.toBuffer()], program.programId);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      const create = async (seed: number, allowedMint: PublicKey | null) => {
+        const dareId = new BN(++dareIdCounter);
+        const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+        const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+        await program.methods
+          .createDare(dareId, fakeHash(seed), new BN(TOKEN_AMOUNT), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: statsPDA, mint: freezable, allowedMint,
+            challengerTokenAccount: ata, vaultTokenAccount: getAssociatedTokenAddressSync(freezable, vaultPDA, true),
+            tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        return darePDA;
+      };
+
+      try {
+        await create(321, null);
+        expect.fail("Should have thrown FreezableMint");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("FreezableMint");
+      }
+
+      await program.methods.allowMint()
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA, mint: freezable, allowedMint: allowedMintPDA, systemProgram: SystemProgram.programId })
+        .rpc();
+      const darePDA = await create(322, allowedMintPDA);
+      expect((await program.account.dare.fetch(darePDA)).mint.toBase58()).to.equal(freezable.toBase58());
+
+      await program.methods.disallowMint()
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA, allowedMint: allowedMintPDA })
+        .rpc();
+      expect(await connection.getAccountInfo(allowedMintPDA)).to.be.null;
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // community vote
+  // --------------------------------------------------------------------------
+
+  describe("community vote", () => {
+    it("opens a voting period on proof submission and records votes", async () => {
+      const { darePDA } = await setupDare("submitted", 120, { winnerSelection: { communityVote: {} } });
+      const [votePDA] = getVotePDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods.castVote(true)
+        .accounts({ voter: outsider.publicKey, dare: darePDA, vote: votePDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.votingEndsAt.toNumber()).to.be.greaterThan(0);
+      expect(dareAccount.votesFor).to.equal(1);
+      expect(dareAccount.votesAgainst).to.equal(0);
+
+      const vote = await program.account.vote.fetch(votePDA);
+      expect(vote.voter.toBase58()).to.equal(outsider.publicKey.toBase58());
+      expect(vote.approve).to.be.true;
+    });
+
+    it("allows only one vote per wallet", async () => {
+      const { darePDA } = await setupDare("submitted", 122, { winnerSelection: { communityVote: {} } });
+      const [votePDA] = getVotePDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods.castVote(false)
+        .accounts({ voter: outsider.publicKey, dare: darePDA, vote: votePDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+
+      try {
+        await program.methods.castVote(false)
+          .accounts({ voter: outsider.publicKey, dare: darePDA, vote: votePDA, systemProgram: SystemProgram.programId })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown AlreadyVoted");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyVoted");
+      }
+    });
+
+    it("rejects votes from the challenger", async () => {
+      const { darePDA } = await setupDare("submitted", 124, { winnerSelection: { communityVote: {} } });
+      const [votePDA] = getVotePDA(program.programId, darePDA, challenger.publicKey);
+
+      try {
+        await program.methods.castVote(false)
+          .accounts({ voter: challenger.publicKey, dare: darePDA, vote: votePDA, systemProgram: SystemProgram.programId })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown IneligibleVoter");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("IneligibleVoter");
+      }
+    });
+
+    it("rejects challenger approval on a CommunityVote dare", async () => {
+      const { darePDA, vaultPDA, dareeStatsPDA } = await setupDare("submitted", 126, { winnerSelection: { communityVote: {} } });
+
+      try {
+        await program.methods.approveDare()
+          .accounts({
+            authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            daree: daree.publicKey, dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown CommunityVoteDare");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CommunityVoteDare");
+      }
+    });
+
+    it("rejects tally while voting is open", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA, dareeStatsPDA } = await setupDare("submitted", 128, { winnerSelection: { communityVote: {} } });
+
+      try {
+        await program.methods.tallyVotes()
+          .accounts({
+            payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+            recipient: daree.publicKey, challengerStats: challengerStatsPDA,
+            dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            config: configPDA, treasury: treasury.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown VotingActive");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("VotingActive");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // bounty submissions
+  // --------------------------------------------------------------------------
+
+  describe("bounty submissions", () => {
+    it("accepts parallel submissions and pays the submission the challenger picks", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const amount = new BN(LAMPORTS_PER_SOL);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+      const [outsiderStatsPDA] = getUserStatsPDA(program.programId, outsider.publicKey);
+      const [dareeSubmissionPDA] = getSubmissionPDA(program.programId, darePDA, daree.publicKey);
+      const [outsiderSubmissionPDA] = getSubmissionPDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(130), amount, futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      // Two submitters compete in parallel
+      await program.methods.submitProof(fakeHash(131))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, submission: dareeSubmissionPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+      await program.methods.submitProof(fakeHash(132))
+        .accounts({ submitter: outsider.publicKey, dare: darePDA, submitterStats: outsiderStatsPDA, submission: outsiderSubmissionPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+
+      let dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.pendingSubmissions).to.equal(2);
+
+      // Reject one, the dare stays open for review
+      await program.methods.rejectDare(fakeHash(0))
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, submission: dareeSubmissionPDA,
+          proofAttempt: getProofAttemptPDA(program.programId, darePDA, 0)[0],
+        })
+        .signers([challenger])
+        .rpc();
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ proofSubmitted: {} });
+      expect(dareAccount.pendingSubmissions).to.equal(1);
+      const rejected = await program.account.submission.fetch(dareeSubmissionPDA);
+      expect(rejected.status).to.deep.equal({ rejected: {} });
+
+      // Approve the other
+      const outsiderBefore = await connection.getBalance(outsider.publicKey);
+      await program.methods.approveDare()
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: outsider.publicKey, dareeStats: outsiderStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, outsider.publicKey)[0],
+          submission: outsiderSubmissionPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ completed: {} });
+      expect(dareAccount.daree.toBase58()).to.equal(outsider.publicKey.toBase58());
+      expect(dareAccount.proofHash).to.deep.equal(fakeHash(132));
+      const outsiderAfter = await connection.getBalance(outsider.publicKey);
+      expect(outsiderAfter - outsiderBefore).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("rejects replacing a pending submission", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [outsiderStatsPDA] = getUserStatsPDA(program.programId, outsider.publicKey);
+      const [submissionPDA] = getSubmissionPDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(133), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.submitProof(fakeHash(134))
+        .accounts({ submitter: outsider.publicKey, dare: darePDA, submitterStats: outsiderStatsPDA, submission: submissionPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+
+      try {
+        await program.methods.submitProof(fakeHash(135))
+          .accounts({ submitter: outsider.publicKey, dare: darePDA, submitterStats: outsiderStatsPDA, submission: submissionPDA, systemProgram: SystemProgram.programId })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown SubmissionPending");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SubmissionPending");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // split payouts
+  // --------------------------------------------------------------------------
+
+  describe("split payouts", () => {
+    it("pays ranked prizes in approval order and the last winner sweeps the rest", async () => {
+      const third = Keypair.generate();
+      await airdrop(connection, third.publicKey);
+
+      const dareId = new BN(++dareIdCounter);
+      const amount = new BN(LAMPORTS_PER_SOL + 1); // odd amount leaves rounding dust
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(140), amount, futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams({ payoutSharesBps: [5000, 3000, 2000] }))
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const winners = [daree, outsider, third];
+      for (const [i, winner] of winners.entries()) {
+        const [statsPDA] = getUserStatsPDA(program.programId, winner.publicKey);
+        const [submissionPDA] = getSubmissionPDA(program.programId, darePDA, winner.publicKey);
+        await program.methods.submitProof(fakeHash(141 + i))
+          .accounts({ submitter: winner.publicKey, dare: darePDA, submitterStats: statsPDA, submission: submissionPDA, systemProgram: SystemProgram.programId })
+          .signers([winner])
+          .rpc();
+      }
+
+      const expected = [
+        Math.floor((LAMPORTS_PER_SOL + 1) * 0.5),
+        Math.floor((LAMPORTS_PER_SOL + 1) * 0.3),
+      ];
+      expected.push(LAMPORTS_PER_SOL + 1 - expected[0] - expected[1]);
+
+      for (const [i, winner] of winners.entries()) {
+        const [statsPDA] = getUserStatsPDA(program.programId, winner.publicKey);
+        const [submissionPDA] = getSubmissionPDA(program.programId, darePDA, winner.publicKey);
+        await program.methods.approveDare()
+          .accounts({
+            authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            daree: winner.publicKey, dareeStats: statsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, winner.publicKey)[0],
+            submission: submissionPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+
+        const stats = await program.account.userStats.fetch(statsPDA);
+        expect(stats.totalEarned.toNumber()).to.equal(expected[i]);
+      }
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ completed: {} });
+      expect(dareAccount.winnersPaid).to.equal(3);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+    });
+
+    it("rejects shares that don't sum to 100%", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      try {
+        await program.methods
+          .createDare(dareId, fakeHash(145), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams({ payoutSharesBps: [5000, 3000] }))
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown InvalidPayoutSchedule");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidPayoutSchedule");
+      }
+    });
+
+    it("rejects a split schedule on a CommunityVote dare", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      try {
+        await program.methods
+          .createDare(dareId, fakeHash(146), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { communityVote: {} }, dareParams({ payoutSharesBps: [5000, 5000] }))
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown InvalidPayoutSchedule");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidPayoutSchedule");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // back_dare / claim_refund
+  // --------------------------------------------------------------------------
+
+  describe("back_dare", () => {
+    it("grows the pot and records the backer's contribution", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [backerPDA] = getBackerPDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(150), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      for (let i = 0; i < 2; i++) {
+        await program.methods.backDare(new BN(LAMPORTS_PER_SOL / 2))
+          .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+          .signers([outsider])
+          .rpc();
+      }
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.amount.toNumber()).to.equal(2 * LAMPORTS_PER_SOL);
+      expect(dareAccount.backedAmount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+      expect(dareAccount.backerCount).to.equal(1);
+
+      const backer = await program.account.backer.fetch(backerPDA);
+      expect(backer.amount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(2 * LAMPORTS_PER_SOL);
+    });
+
+    it("refunds the challenger and backers pro-rata on cancel", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [backerPDA] = getBackerPDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(151), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const backSig = await program.methods.backDare(new BN(3 * LAMPORTS_PER_SOL))
+        .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" });
+
+      const [backed] = await cpiEvents(program, backSig);
+      expect(backed.name).to.equal("dareBacked");
+      expect(backed.data.backer.toBase58()).to.equal(outsider.publicKey.toBase58());
+      expect(backed.data.added.toNumber()).to.equal(3 * LAMPORTS_PER_SOL);
+      expect(backed.data.newAmount.toNumber()).to.equal(4 * LAMPORTS_PER_SOL);
+
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      // Only the challenger's own stake went back to them
+      expect(await connection.getBalance(vaultPDA)).to.equal(3 * LAMPORTS_PER_SOL);
+
+      const backerBefore = await connection.getBalance(outsider.publicKey);
+      const claimSig = await program.methods.claimRefund()
+        .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" });
+
+      const [claimed] = await cpiEvents(program, claimSig);
+      expect(claimed.name).to.equal("refundClaimed");
+      expect(claimed.data.refunded.toNumber()).to.equal(3 * LAMPORTS_PER_SOL);
+      expect(claimed.data.backersRefunded).to.equal(1);
+
+      const backerAfter = await connection.getBalance(outsider.publicKey);
+      expect(backerAfter - backerBefore).to.be.greaterThan(2.99 * LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+      expect(await connection.getAccountInfo(backerPDA)).to.be.null;
+    });
+
+    it("rejects the challenger backing their own dare", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [backerPDA] = getBackerPDA(program.programId, darePDA, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(152), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      try {
+        await program.methods.backDare(new BN(LAMPORTS_PER_SOL))
+          .accounts({ backer: challenger.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown CannotBackOwnDare");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CannotBackOwnDare");
+      }
+    });
+
+    it("rejects refund claims on a dare that wasn't refunded", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [backerPDA] = getBackerPDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(153), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.backDare(new BN(LAMPORTS_PER_SOL))
+        .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+
+      try {
+        await program.methods.claimRefund()
+          .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown DareNotRefunded");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DareNotRefunded");
+      }
+    });
+
+    it("gives a backing left over from a closed dare no claim on a dare recreated at the same address", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+      const [staleBackerPDA] = getBackerPDA(program.programId, darePDA, outsider.publicKey);
+      const [freshBackerPDA] = getBackerPDA(program.programId, darePDA, daree.publicKey);
+
+      // First dare: backed, completed and closed, so the backer never claims and their Backer account stays
+      await program.methods
+        .createDare(dareId, fakeHash(323), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+      await program.methods.backDare(new BN(LAMPORTS_PER_SOL))
+        .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: staleBackerPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+      await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+      await program.methods.submitProof(fakeHash(324))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+      await program.methods.approveDare()
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+      await program.methods.closeDare()
+        .accounts({
+          payer: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challenger: challenger.publicKey, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+      expect(await connection.getAccountInfo(staleBackerPDA)).to.not.be.null;
+
+      // Second dare at the same address, backed by someone else and cancelled
+      await sleep(1000);
+      await program.methods
+        .createDare(dareId, fakeHash(325), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+      await program.methods.backDare(new BN(LAMPORTS_PER_SOL))
+        .accounts({ backer: daree.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: freshBackerPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      // The stale backing is closed without touching the pool
+      await program.methods.claimRefund()
+        .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: staleBackerPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+      expect(await connection.getAccountInfo(staleBackerPDA)).to.be.null;
+      expect(await connection.getBalance(vaultPDA)).to.equal(LAMPORTS_PER_SOL);
+      expect((await program.account.dare.fetch(darePDA)).backersRefunded).to.equal(0);
+
+      // The real backer still gets their whole contribution back
+      const backerBefore = await connection.getBalance(daree.publicKey);
+      await program.methods.claimRefund()
+        .accounts({ backer: daree.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: freshBackerPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+      expect(await connection.getBalance(daree.publicKey) - backerBefore).to.be.greaterThan(0.99 * LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // counter-stake
+  // --------------------------------------------------------------------------
+
+  describe("counter-stake", () => {
+    it("locks the daree's stake on accept and returns it with the reward", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const stake = new BN(LAMPORTS_PER_SOL / 2);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(160), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ dareeStake: stake }))
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.acceptDare()
+        .accounts({
+          daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([daree])
+        .rpc();
+
+      let dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.dareeStake.toNumber()).to.equal(LAMPORTS_PER_SOL / 2);
+      expect(dareAccount.stakeHeld.toNumber()).to.equal(LAMPORTS_PER_SOL / 2);
+      expect(await connection.getBalance(vaultPDA)).to.equal(1.5 * LAMPORTS_PER_SOL);
+
+      await program.methods.submitProof(fakeHash(161))
+        .accounts({
+          submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([daree])
+        .rpc();
+
+      const dareeBalanceBefore = await connection.getBalance(daree.publicKey);
+      await program.methods.approveDare()
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      // Reward plus the stake back; only the reward counts as earnings
+      const dareeBalanceAfter = await connection.getBalance(daree.publicKey);
+      expect(dareeBalanceAfter - dareeBalanceBefore).to.equal(1.5 * LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.stakeHeld.toNumber()).to.equal(0);
+      const stats = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(stats.totalEarned.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("forfeits the stake to the challenger when the daree gives up", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(162), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ targetDaree: daree.publicKey, dareeStake: new BN(LAMPORTS_PER_SOL / 2) }))
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.acceptDare()
+        .accounts({
+          daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([daree])
+        .rpc();
+
+      const challengerBalanceBefore = await connection.getBalance(challenger.publicKey);
+      await program.methods.refuseDare()
+        .accounts({
+          daree: daree.publicKey, dare: darePDA, vault: vaultPDA,
+          challenger: challenger.publicKey, challengerStats: challengerStatsPDA,
+          dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([daree])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ refused: {} });
+
+      // Refund plus the forfeited stake
+      const challengerBalanceAfter = await connection.getBalance(challenger.publicKey);
+      expect(challengerBalanceAfter - challengerBalanceBefore).to.equal(1.5 * LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+
+      const challengerStats = await program.account.userStats.fetch(challengerStatsPDA);
+      expect(challengerStats.totalSpent.toNumber()).to.equal(0);
+      const dareeStats = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(dareeStats.daresFailed).to.equal(1);
+    });
+
+    it("rejects a stake on bounties with parallel submissions", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      try {
+        await program.methods
+          .createDare(dareId, fakeHash(163), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams({ dareeStake: new BN(LAMPORTS_PER_SOL / 2) }))
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown StakeNotSupported");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("StakeNotSupported");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // protocol fee
+  // --------------------------------------------------------------------------
+
+  describe("protocol fee", () => {
+    afterEach(async () => {
+      await program.methods.updateFee(0)
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+    });
+
+    it("takes the fee from the reward on approval and sends it to the treasury", async () => {
+      const feeSig = await program.methods.updateFee(500)
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc({ commitment: "confirmed" });
+
+      const [feeUpdated] = await cpiEvents(program, feeSig);
+      expect(feeUpdated.name).to.equal("feeUpdated");
+      expect(feeUpdated.data.oldFeeBps).to.equal(0);
+      expect(feeUpdated.data.feeBps).to.equal(500);
+
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(170), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      await program.methods.submitProof(fakeHash(171))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      const dareeBefore = await connection.getBalance(daree.publicKey);
+      const treasuryBefore = await connection.getBalance(treasury.publicKey);
+
+      await program.methods.approveDare()
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          config: configPDA, treasury: treasury.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      // 5% of 1 SOL to the treasury, the rest to the daree
+      const dareeAfter = await connection.getBalance(daree.publicKey);
+      const treasuryAfter = await connection.getBalance(treasury.publicKey);
+      expect(treasuryAfter - treasuryBefore).to.equal(0.05 * LAMPORTS_PER_SOL);
+      expect(dareeAfter - dareeBefore).to.equal(0.95 * LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+
+      const stats = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(stats.totalEarned.toNumber()).to.equal(0.95 * LAMPORTS_PER_SOL);
+    });
+
+    it("never charges the fee on refunds", async () => {
+      await program.methods.updateFee(500)
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(172), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const treasuryBefore = await connection.getBalance(treasury.publicKey);
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      expect(await connection.getBalance(treasury.publicKey)).to.equal(treasuryBefore);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+    });
+
+    it("rejects a fee above the on-chain maximum", async () => {
+      try {
+        await program.methods.updateFee(1001)
+          .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+          .rpc();
+        expect.fail("Should have thrown FeeTooHigh");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("FeeTooHigh");
+      }
+    });
+
+    it("rejects fee updates from anyone but the admin", async () => {
+      try {
+        await program.methods.updateFee(100)
+          .accounts({ admin: outsider.publicKey, config: configPDA })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown UnauthorizedAdmin");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // pause
+  // --------------------------------------------------------------------------
+
+  describe("pause", () => {
+    afterEach(async () => {
+      const config = await program.account.config.fetch(configPDA);
+      if (config.paused) {
+        await program.methods.unpause()
+          .accounts({ authority: provider.wallet.publicKey, config: configPDA })
+          .rpc();
+      }
+      await program.methods.updateGuardian(PublicKey.default)
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+    });
+
+    it("blocks new dares while paused but still lets the challenger cancel", async () => {
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      // A dare created before the pause
+      const openId = new BN(++dareIdCounter);
+      const [openPDA] = getDarePDA(program.programId, challenger.publicKey, openId);
+      const [openVaultPDA] = getVaultPDA(program.programId, openPDA);
+      await program.methods
+        .createDare(openId, fakeHash(180), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: openPDA, vault: openVaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.pause()
+        .accounts({ authority: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+      expect((await program.account.config.fetch(configPDA)).paused).to.equal(true);
+
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      try {
+        await program.methods
+          .createDare(dareId, fakeHash(181), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown ProgramPaused");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ProgramPaused");
+      }
+
+      // Exits keep working
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: openPDA, vault: openVaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+      expect(await connection.getBalance(openVaultPDA)).to.equal(0);
+    });
+
+    it("lets the guardian pause and unpause", async () => {
+      await program.methods.updateGuardian(outsider.publicKey)
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+
+      await program.methods.pause()
+        .accounts({ authority: outsider.publicKey, config: configPDA })
+        .signers([outsider])
+        .rpc();
+      expect((await program.account.config.fetch(configPDA)).paused).to.equal(true);
+
+      await program.methods.unpause()
+        .accounts({ authority: outsider.publicKey, config: configPDA })
+        .signers([outsider])
+        .rpc();
+      expect((await program.account.config.fetch(configPDA)).paused).to.equal(false);
+    });
+
+    it("rejects pause from anyone but the admin or guardian", async () => {
+      try {
+        await program.methods.pause()
+          .accounts({ authority: outsider.publicKey, config: configPDA })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown UnauthorizedGuardian");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnauthorizedGuardian");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // disputes
+  // --------------------------------------------------------------------------
+
+  describe("disputes", () => {
+    const arbiter = Keypair.generate();
+
+    before(async () => {
+      await program.methods.updateArbiter(arbiter.publicKey)
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+    });
+
+    it("freezes the dare and lets the arbiter pay the daree", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA, dareeStatsPDA } = await setupDare("disputed", 190);
+
+      let dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ disputed: {} });
+      expect(dareAccount.proofHash).to.deep.equal(fakeHash(191));
+      expect(dareAccount.disputedAt.toNumber()).to.be.greaterThan(0);
+
+      const dareeBefore = await connection.getBalance(daree.publicKey);
+      await program.methods.resolveDispute({ payDaree: {} })
+        .accounts({
+          arbiter: arbiter.publicKey, dare: darePDA, vault: vaultPDA,
+          config: configPDA, treasury: treasury.publicKey,
+          challenger: challenger.publicKey, daree: daree.publicKey,
+          challengerStats: challengerStatsPDA, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([arbiter])
+        .rpc();
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ completed: {} });
+      const dareeAfter = await connection.getBalance(daree.publicKey);
+      expect(dareeAfter - dareeBefore).to.equal(LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+
+      const stats = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(stats.daresCompleted).to.equal(1);
+    });
+
+    it("splits the escrow between daree and challenger", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA, dareeStatsPDA } = await setupDare("disputed", 192);
+
+      const dareeBefore = await connection.getBalance(daree.publicKey);
+      const challengerBefore = await connection.getBalance(challenger.publicKey);
+      await program.methods.resolveDispute({ split: { dareeBps: 4000 } })
+        .accounts({
+          arbiter: arbiter.publicKey, dare: darePDA, vault: vaultPDA,
+          config: configPDA, treasury: treasury.publicKey,
+          challenger: challenger.publicKey, daree: daree.publicKey,
+          challengerStats: challengerStatsPDA, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([arbiter])
+        .rpc();
+
+      const dareeAfter = await connection.getBalance(daree.publicKey);
+      const challengerAfter = await connection.getBalance(challenger.publicKey);
+      expect(dareeAfter - dareeBefore).to.equal(0.4 * LAMPORTS_PER_SOL);
+      expect(challengerAfter - challengerBefore).to.equal(0.6 * LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+    });
+
+    it("rejects rulings from anyone but the arbiter", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA, dareeStatsPDA } = await setupDare("disputed", 194);
+
+      try {
+        await program.methods.resolveDispute({ refundChallenger: {} })
+          .accounts({
+            arbiter: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            config: configPDA, treasury: treasury.publicKey,
+            challenger: challenger.publicKey, daree: daree.publicKey,
+            challengerStats: challengerStatsPDA, dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown UnauthorizedArbiter");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnauthorizedArbiter");
+      }
+    });
+
+    it("keeps the escrow frozen until the arbiter's timeout passes", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA } = await setupDare("disputed", 196);
+
+      try {
+        await program.methods.expireDare()
+          .accounts({
+            payer: outsider.publicKey, dare: darePDA, vault: vaultPDA, recipient: challenger.publicKey,
+            challengerStats: challengerStatsPDA, daree: daree.publicKey,
+            config: configPDA, treasury: treasury.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown ArbiterStillDeciding");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ArbiterStillDeciding");
+      }
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ disputed: {} });
+      expect(await connection.getBalance(vaultPDA)).to.equal(LAMPORTS_PER_SOL);
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // close_dare
+  // --------------------------------------------------------------------------
+
+  describe("close_dare", () => {
+    it("closes a bounty that finished with other submissions still pending", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+      const [outsiderStatsPDA] = getUserStatsPDA(program.programId, outsider.publicKey);
+      const [dareeSubmissionPDA] = getSubmissionPDA(program.programId, darePDA, daree.publicKey);
+      const [outsiderSubmissionPDA] = getSubmissionPDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(326), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { publicBounty: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+      await program.methods.submitProof(fakeHash(327))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, submission: dareeSubmissionPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+      await program.methods.submitProof(fakeHash(328))
+        .accounts({ submitter: outsider.publicKey, dare: darePDA, submitterStats: outsiderStatsPDA, submission: outsiderSubmissionPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+      await program.methods.approveDare()
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: outsider.publicKey, dareeStats: outsiderStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, outsider.publicKey)[0],
+          submission: outsiderSubmissionPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ completed: {} });
+      expect(dareAccount.pendingSubmissions).to.equal(1);
+
+      // The daree's never-reviewed submission no longer pins the rent
+      await program.methods.closeDare()
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+          challenger: challenger.publicKey, systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+      expect(await connection.getAccountInfo(darePDA)).to.be.null;
+    });
+
+    it("closes a cancelled dare and returns the rent to the challenger", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(200), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const rent = await connection.getBalance(darePDA);
+      const challengerBefore = await connection.getBalance(challenger.publicKey);
+
+      // Anyone can crank the close; the rent still goes to the challenger
+      await program.methods.closeDare()
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+          challenger: challenger.publicKey, systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+
+      expect(await connection.getAccountInfo(darePDA)).to.be.null;
+      const challengerAfter = await connection.getBalance(challenger.publicKey);
+      expect(challengerAfter - challengerBefore).to.equal(rent);
+    });
+
+    it("closes the vault's token account on a token dare", async () => {
+      const mint = await createMint(connection, challenger, challenger.publicKey, null, 6);
+      const challengerAta = (
+        await getOrCreateAssociatedTokenAccount(connection, challenger, mint, challenger.publicKey)
+      ).address;
+      await mintTo(connection, challenger, mint, challengerAta, challenger, 10_000_000);
+
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const vaultAta = getAssociatedTokenAddressSync(mint, vaultPDA, true);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(201), new BN(10_000_000), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, mint,
+          challengerTokenAccount: challengerAta, vaultTokenAccount: vaultAta,
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, mint,
+          vaultTokenAccount: vaultAta, challengerTokenAccount: challengerAta,
+          tokenProgram: TOKEN_PROGRAM_ID, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.closeDare()
+        .accounts({
+          payer: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challenger: challenger.publicKey, mint,
+          vaultTokenAccount: vaultAta, challengerTokenAccount: challengerAta,
+          tokenProgram: TOKEN_PROGRAM_ID, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      expect(await connection.getAccountInfo(darePDA)).to.be.null;
+      expect(await connection.getAccountInfo(vaultAta)).to.be.null;
+    });
+
+    it("rejects closing a dare that is still in play", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(202), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      try {
+        await program.methods.closeDare()
+          .accounts({
+            payer: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challenger: challenger.publicKey, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown DareNotTerminal");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DareNotTerminal");
+      }
+    });
+
+    it("keeps the dare open until every backer has claimed", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [backerPDA] = getBackerPDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(203), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.backDare(new BN(LAMPORTS_PER_SOL))
+        .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      try {
+        await program.methods.closeDare()
+          .accounts({
+            payer: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challenger: challenger.publicKey, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown RefundsOutstanding");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RefundsOutstanding");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // events
+  // --------------------------------------------------------------------------
+
+  describe("events", () => {
+    it("emits typed events through the dare lifecycle", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [challengerStatsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      const createSig = await program.methods
+        .createDare(dareId, fakeHash(210), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: challengerStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc({ commitment: "confirmed" });
+
+      const [created] = await cpiEvents(program, createSig);
+      expect(created.name).to.equal("dareCreated");
+      expect(created.data.dare.toBase58()).to.equal(darePDA.toBase58());
+      expect(created.data.challenger.toBase58()).to.equal(challenger.publicKey.toBase58());
+      expect(created.data.amount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+      expect(created.data.status).to.deep.equal({ created: {} });
+
+      const acceptSig = await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc({ commitment: "confirmed" });
+
+      const [accepted] = await cpiEvents(program, acceptSig);
+      expect(accepted.name).to.equal("dareAccepted");
+      expect(accepted.data.daree.toBase58()).to.equal(daree.publicKey.toBase58());
+      expect(accepted.data.status).to.deep.equal({ active: {} });
+
+      await program.methods.submitProof(fakeHash(211))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      const approveSig = await program.methods.approveDare()
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc({ commitment: "confirmed" });
+
+      const [approved] = await cpiEvents(program, approveSig);
+      expect(approved.name).to.equal("dareApproved");
+      expect(approved.data.amount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+      expect(approved.data.fee.toNumber()).to.equal(0);
+      expect(approved.data.status).to.deep.equal({ completed: {} });
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // deadline extension
+  // --------------------------------------------------------------------------
+
+  describe("deadline extension", () => {
+    it("moves the deadline once the other side accepts", async () => {
+      const { darePDA } = await setupDare("active", 220);
+      const newDeadline = futureTimestamp(3 * 86400);
+
+      await program.methods.proposeDeadlineExtension(newDeadline)
+        .accounts({ proposer: daree.publicKey, dare: darePDA })
+        .signers([daree])
+        .rpc();
+
+      let dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.proposedDeadline.toNumber()).to.equal(newDeadline.toNumber());
+      expect(dareAccount.proposedBy.toBase58()).to.equal(daree.publicKey.toBase58());
+
+      await program.methods.acceptDeadlineExtension()
+        .accounts({ accepter: challenger.publicKey, dare: darePDA })
+        .signers([challenger])
+        .rpc();
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.deadline.toNumber()).to.equal(newDeadline.toNumber());
+      expect(dareAccount.proposedDeadline.toNumber()).to.equal(0);
+    });
+
+    it("rejects accepting your own proposal", async () => {
+      const { darePDA } = await setupDare("active", 221);
+
+      await program.methods.proposeDeadlineExtension(futureTimestamp(2 * 86400))
+        .accounts({ proposer: daree.publicKey, dare: darePDA })
+        .signers([daree])
+        .rpc();
+
+      try {
+        await program.methods.acceptDeadlineExtension()
+          .accounts({ accepter: daree.publicKey, dare: darePDA })
+          .signers([daree])
+          .rpc();
+        expect.fail("Should have thrown CannotAcceptOwnProposal");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CannotAcceptOwnProposal");
+      }
+    });
+
+    it("rejects a deadline beyond the maximum duration from creation", async () => {
+      const { darePDA } = await setupDare("active", 222);
+
+      try {
+        await program.methods.proposeDeadlineExtension(futureTimestamp(31 * 86400))
+          .accounts({ proposer: challenger.publicKey, dare: darePDA })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown DeadlineTooFar");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DeadlineTooFar");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // increase_stake
+  // --------------------------------------------------------------------------
+
+  describe("increase_stake", () => {
+    it("raises the pot on an open dare", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(230), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const sig = await program.methods.increaseStake(new BN(LAMPORTS_PER_SOL))
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc({ commitment: "confirmed" });
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.amount.toNumber()).to.equal(2 * LAMPORTS_PER_SOL);
+      expect(await connection.getBalance(vaultPDA)).to.equal(2 * LAMPORTS_PER_SOL);
+
+      const stats = await program.account.userStats.fetch(statsPDA);
+      expect(stats.totalSpent.toNumber()).to.equal(2 * LAMPORTS_PER_SOL);
+
+      const [raised] = await cpiEvents(program, sig);
+      expect(raised.name).to.equal("stakeIncreased");
+      expect(raised.data.newAmount.toNumber()).to.equal(2 * LAMPORTS_PER_SOL);
+    });
+
+    it("rejects a top-up once proof is under review", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(231), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      await program.methods.submitProof(fakeHash(232))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      try {
+        await program.methods.increaseStake(new BN(LAMPORTS_PER_SOL))
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown InvalidDareStatus");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // milestones
+  // --------------------------------------------------------------------------
+
+  describe("milestones", () => {
+    it("releases each milestone's share as it's approved", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(240), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ payoutSharesBps: [2500, 2500, 5000] }))
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      const before = await program.account.userStats.fetch(dareeStatsPDA);
+      const shares = [LAMPORTS_PER_SOL / 4, LAMPORTS_PER_SOL / 4, LAMPORTS_PER_SOL / 2];
+      let remaining = LAMPORTS_PER_SOL;
+
+      for (const [i, share] of shares.entries()) {
+        await program.methods.submitProof(fakeHash(241 + i))
+          .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+          .signers([daree])
+          .rpc();
+
+        await program.methods.approveDare()
+          .accounts({
+            authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            daree: daree.publicKey, dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+
+        remaining -= share;
+        expect(await connection.getBalance(vaultPDA)).to.equal(remaining);
+
+        const dareAccount = await program.account.dare.fetch(darePDA);
+        expect(dareAccount.winnersPaid).to.equal(i + 1);
+        expect(dareAccount.status).to.deep.equal(i < shares.length - 1 ? { active: {} } : { completed: {} });
+
+        const stats = await program.account.userStats.fetch(dareeStatsPDA);
+        expect(stats.milestonesCompleted).to.equal(before.milestonesCompleted + i + 1);
+        expect(stats.daresCompleted).to.equal(before.daresCompleted + (i < shares.length - 1 ? 0 : 1));
+      }
+
+      const after = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(after.totalEarned.toNumber() - before.totalEarned.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("holds the counter-stake until the final milestone", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const stake = LAMPORTS_PER_SOL / 2;
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(245), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ payoutSharesBps: [5000, 5000], dareeStake: new BN(stake) }))
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, vault: vaultPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      const before = await program.account.userStats.fetch(dareeStatsPDA);
+      for (const i of [0, 1]) {
+        await program.methods.submitProof(fakeHash(246 + i))
+          .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+          .signers([daree])
+          .rpc();
+
+        await program.methods.approveDare()
+          .accounts({
+            authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            daree: daree.publicKey, dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+
+        if (i === 0) {
+          expect(await connection.getBalance(vaultPDA)).to.equal(LAMPORTS_PER_SOL / 2 + stake);
+        }
+      }
+
+      // Stake comes back with the last milestone but only the reward counts as earned
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+      const after = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(after.totalEarned.toNumber() - before.totalEarned.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("sweeps a backer's share below rent exemption into the refund when a part-paid dare expires", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const backing = LAMPORTS_PER_SOL / 1000;
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+      const [backerPDA] = getBackerPDA(program.programId, darePDA, outsider.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(343), new BN(LAMPORTS_PER_SOL), futureTimestamp(4), { directDare: {} }, { challengerSelect: {} }, dareParams({ payoutSharesBps: [5000, 5000] }))
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+      await program.methods.backDare(new BN(backing))
+        .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+      await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+      await program.methods.submitProof(fakeHash(344))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+      await program.methods.approveDare()
+        .accounts({
+          authority: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      // Half of the pot is left; the backer's slice of it is under a vault's rent-exempt minimum
+      const left = (LAMPORTS_PER_SOL + backing) / 2;
+      expect(await connection.getBalance(vaultPDA)).to.equal(left);
+      expect(backing / 2).to.be.lessThan(await connection.getMinimumBalanceForRentExemption(0));
+
+      await sleep(5000);
+      const challengerBefore = await connection.getBalance(challenger.publicKey);
+      await program.methods.expireDare()
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA, recipient: challenger.publicKey,
+          challengerStats: statsPDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+
+      expect(await connection.getBalance(challenger.publicKey)).to.equal(challengerBefore + left);
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+
+      // The backer's claim still goes through and closes their Backer account
+      await program.methods.claimRefund()
+        .accounts({ backer: outsider.publicKey, dare: darePDA, vault: vaultPDA, backerAccount: backerPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+      expect(await connection.getAccountInfo(backerPDA)).to.be.null;
+      expect((await program.account.dare.fetch(darePDA)).backersRefunded).to.equal(1);
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // streaks
+  // --------------------------------------------------------------------------
+
+  describe("streaks", () => {
+    // Short periods so the tests can wait them out: the streak opens 2s after creation
+    const streak = (period: number, periods: number, missPolicy: any) => ({
+      deadline: futureTimestamp(2 + period * periods),
+      params: { streakPeriod: new BN(period), streakPeriods: periods, missPolicy },
+    });
+
+    it("records one check-in per period and stays Active until the last", async () => {
+      const { darePDA, dareeStatsPDA } = await setupDare("active", 250, streak(86400, 2, { endDare: {} }));
+
+      try {
+        await program.methods.submitProof(fakeHash(251))
+          .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+          .signers([daree])
+          .rpc();
+        expect.fail("Should have thrown StreakNotStarted");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("StreakNotStarted");
+      }
+
+      await sleep(3000);
+      const sig = await program.methods.submitProof(fakeHash(251))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc({ commitment: "confirmed" });
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ active: {} });
+      expect(dareAccount.checkins.toNumber()).to.equal(1);
+
+      const events = await cpiEvents(program, sig);
+      const checkIn = events.find((e) => e.name === "streakCheckIn");
+      expect(checkIn.data.period).to.equal(0);
+
+      try {
+        await program.methods.submitProof(fakeHash(252))
+          .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+          .signers([daree])
+          .rpc();
+        expect.fail("Should have thrown AlreadyCheckedIn");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyCheckedIn");
+      }
+    });
+
+    it("forfeits a slice for a missed period and keeps the streak going", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA: statsPDA, dareeStatsPDA } = await setupDare("active", 253, streak(4, 2, { forfeitSlice: {} }));
+
+      // Let the first period close without a check-in
+      await sleep(7000);
+      try {
+        await program.methods.submitProof(fakeHash(254))
+          .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+          .signers([daree])
+          .rpc();
+        expect.fail("Should have thrown MissedPeriodsUnsettled");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MissedPeriodsUnsettled");
+      }
+
+      const challengerBefore = await connection.getBalance(challenger.publicKey);
+      await program.methods.expireDare()
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA, recipient: challenger.publicKey,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+
+      expect(await connection.getBalance(challenger.publicKey)).to.equal(challengerBefore + LAMPORTS_PER_SOL / 2);
+      let dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ active: {} });
+      expect(dareAccount.missedPeriods).to.equal(1);
+
+      // The final check-in goes to the challenger for review of what's left
+      await program.methods.submitProof(fakeHash(254))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ proofSubmitted: {} });
+      expect(dareAccount.checkins.toNumber()).to.equal(2);
+      expect(await connection.getBalance(vaultPDA)).to.equal(LAMPORTS_PER_SOL / 2);
+    });
+
+    it("ends the dare on a missed period under EndDare", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA: statsPDA, dareeStatsPDA } = await setupDare("active", 255, streak(4, 2, { endDare: {} }));
+      const before = await program.account.userStats.fetch(dareeStatsPDA);
+
+      await sleep(7000);
+      await program.methods.expireDare()
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA, recipient: challenger.publicKey,
+          challengerStats: statsPDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ expired: {} });
+      expect(await connection.getBalance(vaultPDA)).to.equal(0);
+
+      const after = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(after.daresFailed).to.equal(before.daresFailed + 1);
+    });
+
+    it("rejects a streak that doesn't fit before the deadline", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      try {
+        await program.methods
+          .createDare(dareId, fakeHash(256), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams({ streakPeriod: new BN(86400), streakPeriods: 3 }))
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown InvalidStreak");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidStreak");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // judge
+  // --------------------------------------------------------------------------
+
+  describe("judge", () => {
+    it("lets only the judge settle and pays them their fee", async () => {
+      const judge = outsider;
+      const { darePDA, vaultPDA, dareeStatsPDA } = await setupDare("submitted", 338, {
+        params: { judge: judge.publicKey, judgeFeeBps: 500 },
+      });
+      const approve = (authority: Keypair) => program.methods.approveDare()
+        .accounts({
+          authority: authority.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      try {
+        await approve(challenger);
+        expect.fail("Should have thrown UnauthorizedJudge");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnauthorizedJudge");
+      }
+
+      const dareeBefore = await connection.getBalance(daree.publicKey);
+      await approve(judge);
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ completed: {} });
+      expect(await connection.getBalance(daree.publicKey)).to.equal(dareeBefore + LAMPORTS_PER_SOL * 0.95);
+      const [judgeStatsPDA] = getUserStatsPDA(program.programId, judge.publicKey);
+      const judgeStats = await program.account.userStats.fetch(judgeStatsPDA);
+      expect(judgeStats.daresJudged).to.equal(1);
+      expect(judgeStats.judgeFeesEarned.toNumber()).to.equal(LAMPORTS_PER_SOL * 0.05);
+    });
+
+    it("pays the judge their fee when the arbiter upholds their rejection", async () => {
+      const judge = outsider;
+      const arbiter = Keypair.generate();
+      await program.methods.updateArbiter(arbiter.publicKey)
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+      const { darePDA, vaultPDA, challengerStatsPDA, dareeStatsPDA, proofAttemptPDA } = await setupDare("submitted", 340, {
+        params: { judge: judge.publicKey, judgeFeeBps: 500 },
+      });
+
+      await program.methods.rejectDare(fakeHash(342))
+        .accounts({ authority: judge.publicKey, dare: darePDA, proofAttempt: proofAttemptPDA })
+        .signers([judge])
+        .rpc();
+      await program.methods.disputeRejection()
+        .accounts({ daree: daree.publicKey, dare: darePDA, config: configPDA })
+        .signers([daree])
+        .rpc();
+
+      const [judgeStatsPDA] = getUserStatsPDA(program.programId, judge.publicKey);
+      const earnedBefore = (await program.account.userStats.fetch(judgeStatsPDA)).judgeFeesEarned.toNumber();
+      const judgeBefore = await connection.getBalance(judge.publicKey);
+      const challengerBefore = await connection.getBalance(challenger.publicKey);
+      await program.methods.resolveDispute({ refundChallenger: {} })
+        .accounts({
+          arbiter: arbiter.publicKey, dare: darePDA, vault: vaultPDA,
+          config: configPDA, treasury: treasury.publicKey,
+          challenger: challenger.publicKey, daree: daree.publicKey,
+          challengerStats: challengerStatsPDA, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          judge: judge.publicKey, judgeStats: judgeStatsPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([arbiter])
+        .rpc();
+
+      expect(await connection.getBalance(judge.publicKey)).to.equal(judgeBefore + LAMPORTS_PER_SOL * 0.05);
+      expect(await connection.getBalance(challenger.publicKey)).to.equal(challengerBefore + LAMPORTS_PER_SOL * 0.95);
+      const judgeStats = await program.account.userStats.fetch(judgeStatsPDA);
+      expect(judgeStats.judgeFeesEarned.toNumber()).to.equal(earnedBefore + LAMPORTS_PER_SOL * 0.05);
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // oracle attestations
+  // --------------------------------------------------------------------------
+
+  describe("oracle attestations", () => {
+    const oracle = Keypair.generate();
+
+    before(async () => {
+      await program.methods.updateOracle(oracle.publicKey)
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+    });
+
+    it("pays the daree on an approving attestation, without the challenger", async () => {
+      const { darePDA, vaultPDA, dareeStatsPDA, createdAt, proofHash } = await setupDare("submitted", 270);
+      const dareeBefore = await connection.getBalance(daree.publicKey);
+
+      const sig = await program.methods.settleWithAttestation(true)
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY, systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([attestationIx(oracle, darePDA, createdAt, proofHash, 0, 0, true)])
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" });
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ completed: {} });
+      expect(await connection.getBalance(daree.publicKey)).to.equal(dareeBefore + LAMPORTS_PER_SOL);
+
+      const events = await cpiEvents(program, sig);
+      const attested = events.find((e) => e.name === "proofAttested");
+      expect(attested.data.approved).to.equal(true);
+      expect(attested.data.oracle.toBase58()).to.equal(oracle.publicKey.toBase58());
+    });
+
+    it("rejects the proof on a rejecting attestation", async () => {
+      const { darePDA, vaultPDA, dareeStatsPDA, createdAt, proofHash } = await setupDare("submitted", 272);
+
+      await program.methods.settleWithAttestation(false)
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+          proofAttempt: getProofAttemptPDA(program.programId, darePDA, 0)[0],
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY, systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([attestationIx(oracle, darePDA, createdAt, proofHash, 0, 0, false)])
+        .signers([outsider])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ rejected: {} });
+      expect(await connection.getBalance(vaultPDA)).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("refuses to replay a rejecting attestation against a resubmission of the same proof", async () => {
+      const { darePDA, vaultPDA, dareeStatsPDA, createdAt, proofHash } = await setupDare("submitted", 330);
+      const rejectAttempt0 = attestationIx(oracle, darePDA, createdAt, proofHash, 0, 0, false);
+
+      await program.methods.settleWithAttestation(false)
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+          daree: daree.publicKey, dareeStats: dareeStatsPDA,
+          proofAttempt: getProofAttemptPDA(program.programId, darePDA, 0)[0],
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY, systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([rejectAttempt0])
+        .signers([outsider])
+        .rpc();
+
+      // The daree resubmits the very same proof, which is a new attempt needing a new verdict
+      await program.methods.submitProof(proofHash)
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      try {
+        await program.methods.settleWithAttestation(false)
+          .accounts({
+            payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+            daree: daree.publicKey, dareeStats: dareeStatsPDA,
+            proofAttempt: getProofAttemptPDA(program.programId, darePDA, 1)[0],
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY, systemProgram: SystemProgram.programId,
+          })
+          .preInstructions([rejectAttempt0])
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown InvalidAttestation");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidAttestation");
+      }
+    });
+
+    it("rejects an attestation from another key", async () => {
+      const { darePDA, vaultPDA, dareeStatsPDA, createdAt, proofHash } = await setupDare("submitted", 274);
+
+      try {
+        await program.methods.settleWithAttestation(true)
+          .accounts({
+            payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+            daree: daree.publicKey, dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY, systemProgram: SystemProgram.programId,
+          })
+          .preInstructions([attestationIx(outsider, darePDA, createdAt, proofHash, 0, 0, true)])
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown InvalidAttestation");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidAttestation");
+      }
+    });
+
+    it("rejects an attestation for the opposite verdict", async () => {
+      const { darePDA, vaultPDA, dareeStatsPDA, createdAt, proofHash } = await setupDare("submitted", 276);
+
+      try {
+        await program.methods.settleWithAttestation(true)
+          .accounts({
+            payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+            daree: daree.publicKey, dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY, systemProgram: SystemProgram.programId,
+          })
+          .preInstructions([attestationIx(oracle, darePDA, createdAt, proofHash, 0, 0, false)])
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown InvalidAttestation");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidAttestation");
+      }
+    });
+
+    it("requires the signature instruction", async () => {
+      const { darePDA, vaultPDA, dareeStatsPDA } = await setupDare("submitted", 278);
+
+      try {
+        await program.methods.settleWithAttestation(true)
+          .accounts({
+            payer: outsider.publicKey, dare: darePDA, vault: vaultPDA,
+            daree: daree.publicKey, dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY, systemProgram: SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown MissingAttestation");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MissingAttestation");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // reveal_description
+  // --------------------------------------------------------------------------
+
+  describe("reveal_description", () => {
+    const description = "Eat a whole lemon without making a face";
+
+    it("reveals a description matching the committed hash", async () => {
+      const { darePDA } = await setupDare("created", 0, { descriptionHash: descriptionHash(description) });
+
+      const sig = await program.methods.revealDescription(description)
+        .accounts({ revealer: outsider.publicKey, dare: darePDA })
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" });
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.descriptionRevealed).to.be.true;
+      expect(dareAccount.revealedAt.toNumber()).to.be.greaterThan(0);
+
+      const [revealed] = await cpiEvents(program, sig);
+      expect(revealed.name).to.equal("descriptionRevealed");
+      expect(revealed.data.description).to.equal(description);
+      expect(revealed.data.revealedBy.toBase58()).to.equal(outsider.publicKey.toBase58());
+    });
+
+    it("rejects text that doesn't match the hash", async () => {
+      const { darePDA } = await setupDare("created", 0, { descriptionHash: descriptionHash(description) });
+
+      try {
+        await program.methods.revealDescription("Eat half a lemon")
+          .accounts({ revealer: daree.publicKey, dare: darePDA })
+          .signers([daree])
+          .rpc();
+        expect.fail("Should have thrown DescriptionMismatch");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DescriptionMismatch");
+      }
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.descriptionRevealed).to.be.false;
+    });
+
+    it("rejects descriptions longer than MAX_DESCRIPTION_LEN", async () => {
+      const { darePDA } = await setupDare("created", 0, { descriptionHash: descriptionHash(description) });
+
+      try {
+        await program.methods.revealDescription("x".repeat(801))
+          .accounts({ revealer: provider.wallet.publicKey, dare: darePDA })
+          .rpc();
+        expect.fail("Should have thrown DescriptionTooLong");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DescriptionTooLong");
+      }
+    });
+
+    it("can only be revealed once", async () => {
+      const { darePDA } = await setupDare("created", 0, { descriptionHash: descriptionHash(description) });
+
+      await program.methods.revealDescription(description)
+        .accounts({ revealer: challenger.publicKey, dare: darePDA })
+        .signers([challenger])
+        .rpc();
+
+      try {
+        await program.methods.revealDescription(description)
+          .accounts({ revealer: daree.publicKey, dare: darePDA })
+          .signers([daree])
+          .rpc();
+        expect.fail("Should have thrown DescriptionAlreadyRevealed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DescriptionAlreadyRevealed");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // proof history
+  // --------------------------------------------------------------------------
+
+  describe("proof history", () => {
+    it("keeps a rejected attempt when the daree re-submits", async () => {
+      const { darePDA, dareeStatsPDA, proofAttemptPDA: firstAttemptPDA, sig } = await setupDare("rejected", 280);
+      const [secondAttemptPDA] = getProofAttemptPDA(program.programId, darePDA, 1);
+
+      const [rejectedEvent] = await cpiEvents(program, sig);
+      expect(rejectedEvent.name).to.equal("proofRejected");
+      expect(rejectedEvent.data.attempt).to.equal(0);
+      expect(rejectedEvent.data.reasonHash).to.deep.equal(fakeHash(282));
+
+      await program.methods.submitProof(fakeHash(283))
+        .accounts({
+          submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA,
+          proofAttempt: secondAttemptPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([daree])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.proofAttempts).to.equal(2);
+      expect(dareAccount.proofHash).to.deep.equal(fakeHash(283));
+
+      const first = await program.account.proofAttempt.fetch(firstAttemptPDA);
+      expect(first.index).to.equal(0);
+      expect(first.submitter.toBase58()).to.equal(daree.publicKey.toBase58());
+      expect(first.proofHash).to.deep.equal(fakeHash(281));
+      expect(first.rejectedAt.toNumber()).to.be.greaterThan(0);
+      expect(first.hasRejectionReason).to.be.true;
+      expect(first.rejectionReasonHash).to.deep.equal(fakeHash(282));
+
+      const second = await program.account.proofAttempt.fetch(secondAttemptPDA);
+      expect(second.index).to.equal(1);
+      expect(second.proofHash).to.deep.equal(fakeHash(283));
+      expect(second.rejectedAt.toNumber()).to.equal(0);
+      expect(second.hasRejectionReason).to.be.false;
+    });
+
+    it("only rejects the attempt under review", async () => {
+      const { darePDA, dareeStatsPDA, proofAttemptPDA: firstAttemptPDA } = await setupDare("rejected", 284);
+      const [secondAttemptPDA] = getProofAttemptPDA(program.programId, darePDA, 1);
+
+      await program.methods.submitProof(fakeHash(287))
+        .accounts({
+          submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA,
+          proofAttempt: secondAttemptPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([daree])
+        .rpc();
+
+      try {
+        await program.methods.rejectDare(fakeHash(0))
+          .accounts({ authority: challenger.publicKey, dare: darePDA, proofAttempt: firstAttemptPDA })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown InvalidProofAttempt");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidProofAttempt");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // dispute window
+  // --------------------------------------------------------------------------
+
+  describe("dispute window", () => {
+    const HOUR = 60 * 60;
+
+    after(async () => {
+      await program.methods.updateDisputeWindowBounds(new BN(24 * HOUR), new BN(7 * 24 * HOUR))
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+    });
+
+    it("runs the review window from proof submission", async () => {
+      const { darePDA, vaultPDA } = await setupDare("created", 290, { params: { disputeWindow: new BN(48 * HOUR) } });
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      let dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.disputeWindow.toNumber()).to.equal(48 * HOUR);
+      expect(dareAccount.proofSubmittedAt.toNumber()).to.equal(0);
+
+      await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      await program.methods.submitProof(fakeHash(291))
+        .accounts({ submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc();
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.proofSubmittedAt.toNumber()).to.be.greaterThan(0);
+
+      try {
+        await program.methods.expireDare()
+          .accounts({
+            payer: outsider.publicKey, dare: darePDA, vault: vaultPDA, recipient: daree.publicKey,
+            dareeStats: dareeStatsPDA,
+            dealings: getDealingsPDA(program.programId, challenger.publicKey, daree.publicKey)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown DisputeWindowActive");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DisputeWindowActive");
+      }
+    });
+
+    it("runs the dispute window from the rejection", async () => {
+      const { darePDA } = await setupDare("rejected", 339);
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.rejectedAt.toNumber()).to.be.greaterThan(0);
+      expect(dareAccount.rejectedAt.toNumber()).to.be.at.least(dareAccount.proofSubmittedAt.toNumber());
+    });
+
+    it("uses the default window when none is given", async () => {
+      const { darePDA } = await setupDare("created", 292, { params: { disputeWindow: new BN(0) } });
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.disputeWindow.toNumber()).to.equal(72 * HOUR);
+    });
+
+    it("rejects a window outside the config bounds", async () => {
+      try {
+        await setupDare("created", 293, { params: { disputeWindow: new BN(HOUR) } });
+        expect.fail("Should have thrown InvalidDisputeWindow");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidDisputeWindow");
+      }
+    });
+
+    it("lets the admin widen the bounds", async () => {
+      await program.methods.updateDisputeWindowBounds(new BN(HOUR), new BN(14 * 24 * HOUR))
+        .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+        .rpc();
+
+      const { darePDA } = await setupDare("created", 294, { params: { disputeWindow: new BN(HOUR) } });
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.disputeWindow.toNumber()).to.equal(HOUR);
+
+      try {
+        await program.methods.updateDisputeWindowBounds(new BN(60), new BN(HOUR))
+          .accounts({ admin: provider.wallet.publicKey, config: configPDA })
+          .rpc();
+        expect.fail("Should have thrown InvalidDisputeWindow");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidDisputeWindow");
+      }
+    });
+
+    it("rejects bounds from a non-admin", async () => {
+      try {
+        await program.methods.updateDisputeWindowBounds(new BN(HOUR), new BN(HOUR))
+          .accounts({ admin: outsider.publicKey, config: configPDA })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown UnauthorizedAdmin");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // acceptance window
+  // --------------------------------------------------------------------------
+
+  describe("acceptance window", () => {
+    const DAY = 24 * 60 * 60;
+
+    it("starts the completion clock on acceptance", async () => {
+      const acceptBy = futureTimestamp(DAY);
+      const { darePDA } = await setupDare("created", 300, { deadline: acceptBy, params: { timeToComplete: new BN(3 * DAY) } });
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      let dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.acceptBy.toNumber()).to.equal(acceptBy.toNumber());
+      expect(dareAccount.deadline.toNumber()).to.equal(acceptBy.toNumber());
+
+      const sig = await program.methods.acceptDare()
+        .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([daree])
+        .rpc({ commitment: "confirmed" });
+
+      dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.deadline.toNumber()).to.equal(dareAccount.acceptedAt.toNumber() + 3 * DAY);
+      expect(dareAccount.acceptBy.toNumber()).to.equal(acceptBy.toNumber());
+
+      const [accepted] = await cpiEvents(program, sig);
+      expect(accepted.name).to.equal("dareAccepted");
+      expect(accepted.data.deadline.toNumber()).to.equal(dareAccount.deadline.toNumber());
+    });
+
+    it("starts a bounty's clock on its first submission", async () => {
+      const { darePDA } = await setupDare("created", 301, { deadline: futureTimestamp(DAY), dareType: { publicBounty: {} }, params: { timeToComplete: new BN(2 * DAY) } });
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+      const [submissionPDA] = getSubmissionPDA(program.programId, darePDA, daree.publicKey);
+
+      await program.methods.submitProof(fakeHash(302))
+        .accounts({
+          submitter: daree.publicKey, dare: darePDA, submitterStats: dareeStatsPDA,
+          submission: submissionPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([daree])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.acceptedAt.toNumber()).to.be.greaterThan(0);
+      expect(dareAccount.deadline.toNumber()).to.equal(dareAccount.acceptedAt.toNumber() + 2 * DAY);
+    });
+
+    it("refunds the challenger when nobody accepts in time", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA: statsPDA } = await setupDare("created", 303, { deadline: futureTimestamp(2), params: { timeToComplete: new BN(DAY) } });
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      await sleep(3000);
+
+      try {
+        await program.methods.acceptDare()
+          .accounts({ daree: daree.publicKey, dare: darePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+          .signers([daree])
+          .rpc();
+        expect.fail("Should have thrown DareExpired");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DareExpired");
+      }
+
+      const sig = await program.methods.expireDare()
+        .accounts({
+          payer: outsider.publicKey, dare: darePDA, vault: vaultPDA, recipient: challenger.publicKey,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" });
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ expired: {} });
+
+      const [expired] = await cpiEvents(program, sig);
+      expect(expired.name).to.equal("dareExpired");
+      expect(expired.data.accepted).to.be.false;
+      expect(expired.data.refunded.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("rejects a completion time over 30 days", async () => {
+      try {
+        await setupDare("created", 304, { deadline: futureTimestamp(DAY), params: { timeToComplete: new BN(31 * DAY) } });
+        expect.fail("Should have thrown InvalidTimeToComplete");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidTimeToComplete");
+      }
+    });
+  });
+
+  // --------------------------------------------------------------------------
+  // assigned dare IDs
+  // --------------------------------------------------------------------------
+
+  describe("assigned dare IDs", () => {
+    it("assigns IDs from the challenger's counter and returns them", async () => {
+      const first = await setupDare("created", 310, { dareId: new BN(0) });
+      const second = await setupDare("created", 311, { dareId: new BN(0) });
+
+      expect(first.dareId.toString()).to.equal(ASSIGNED_FLAG.toString());
+      expect(second.dareId.toString()).to.equal(ASSIGNED_FLAG.addn(1).toString());
+      expect((await returnedU64(connection, first.sig)).toString()).to.equal(first.dareId.toString());
+      expect((await returnedU64(connection, second.sig)).toString()).to.equal(second.dareId.toString());
+
+      const dareAccount = await program.account.dare.fetch(second.darePDA);
+      expect(dareAccount.dareId.toString()).to.equal(second.dareId.toString());
+
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const stats = await program.account.userStats.fetch(statsPDA);
+      expect(stats.nextDareId.toNumber()).to.equal(2);
+      expect(stats.daresCreated).to.equal(2);
+    });
+
+    it("lets only one of two creates that read the same counter value through", async () => {
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const stats = await program.account.userStats.fetchNullable(statsPDA);
+      const dareId = ASSIGNED_FLAG.or(stats ? stats.nextDareId : new BN(0));
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+
+      // Both clients derived the dare address from the same counter value and send at once
+      const create = (seed: number) => program.methods
+        .createDare(new BN(0), fakeHash(seed), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+      const results = await Promise.allSettled([create(331), create(332)]);
+
+      expect(results.filter((r) => r.status === "fulfilled")).to.have.length(1);
+      const [failed] = results.filter((r): r is PromiseRejectedResult => r.status === "rejected");
+      // The loser's transaction sees the bumped counter and no longer derives the address it passed
+      expect(failed.reason.error.errorCode.code).to.equal("ConstraintSeeds");
+
+      const after = await program.account.userStats.fetch(statsPDA);
+      expect(after.nextDareId.toString()).to.equal(dareId.sub(ASSIGNED_FLAG).addn(1).toString());
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.dareId.toString()).to.equal(dareId.toString());
+    });
+
+    it("keeps the dare as the second account, where the indexer reads it", async () => {
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, ASSIGNED_FLAG);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+
+      const ix = await program.methods
+        .createDare(new BN(0), fakeHash(333), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+      expect(ix.keys[0].pubkey.toBase58()).to.equal(challenger.publicKey.toBase58());
+      expect(ix.keys[1].pubkey.toBase58()).to.equal(darePDA.toBase58());
+    });
+
+    it("still creates and addresses dares with client-picked IDs", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      const sig = await program.methods
+        .createDare(dareId, fakeHash(312), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc({ commitment: "confirmed" });
+
+      expect((await returnedU64(connection, sig)).toString()).to.equal(dareId.toString());
+      const stats = await program.account.userStats.fetch(statsPDA);
+      expect(stats.nextDareId.toNumber()).to.equal(0);
+
+      await program.methods.cancelDare()
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.status).to.deep.equal({ cancelled: {} });
+    });
+
+    it("rejects client-picked IDs in the assigned range", async () => {
+      const dareId = ASSIGNED_FLAG.addn(7);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      try {
+        await program.methods
+          .createDare(dareId, fakeHash(313), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+          .accounts({
+            challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+            challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        expect.fail("Should have thrown InvalidDareId");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidDareId");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // migration
+  // ---------------------------------------------------------------------------
+  describe("migration", () => {
+    it("creates dares and stats on the current version", async () => {
+      const dareId = new BN(++dareIdCounter);
+      const [darePDA] = getDarePDA(program.programId, challenger.publicKey, dareId);
+      const [vaultPDA] = getVaultPDA(program.programId, darePDA);
+      const [statsPDA] = getUserStatsPDA(program.programId, challenger.publicKey);
+
+      await program.methods
+        .createDare(dareId, fakeHash(314), new BN(LAMPORTS_PER_SOL), futureTimestamp(86400), { directDare: {} }, { challengerSelect: {} }, dareParams())
+        .accounts({
+          challenger: challenger.publicKey, dare: darePDA, vault: vaultPDA,
+          challengerStats: statsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.version).to.equal(1);
+      const stats = await program.account.userStats.fetch(statsPDA);
+      expect(stats.version).to.equal(1);
+
+      try {
+        await program.methods.migrateDare()
+          .accounts({
+            payer: outsider.publicKey, dare: darePDA, proofAttempt: null, submission: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown AlreadyMigrated");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+      }
+
+      try {
+        await program.methods.migrateUserStats()
+          .accounts({ payer: outsider.publicKey, userStats: statsPDA, systemProgram: SystemProgram.programId })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown AlreadyMigrated");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+      }
+    });
+
+    // Loaded into the validator from tests/fixtures (see Anchor.toml), exactly as version 0 left them
+    const legacyUser = Keypair.fromSeed(new Uint8Array(32).fill(7));
+    const legacyDareId = new BN(900001);
+    const [legacyDarePDA] = getDarePDA(program.programId, legacyUser.publicKey, legacyDareId);
+    const [legacyStatsPDA] = getUserStatsPDA(program.programId, legacyUser.publicKey);
+
+    it("reports AccountNotMigrated for a version 0 dare until it is migrated", async () => {
+      const before = await connection.getAccountInfo(legacyDarePDA);
+      expect(before.data.length).to.equal(191);
+
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+      try {
+        await program.methods
+          .acceptDare()
+          .accounts({ daree: daree.publicKey, dare: legacyDarePDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId })
+          .signers([daree])
+          .rpc();
+        expect.fail("Should have thrown AccountNotMigrated");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AccountNotMigrated");
+      }
+
+      await program.methods.migrateDare()
+        .accounts({
+          payer: outsider.publicKey, dare: legacyDarePDA, proofAttempt: null, submission: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+
+      const after = await connection.getAccountInfo(legacyDarePDA);
+      expect(after.data.length).to.equal(509);
+      const dareAccount = await program.account.dare.fetch(legacyDarePDA);
+      expect(dareAccount.version).to.equal(1);
+      expect(dareAccount.challenger.toBase58()).to.equal(legacyUser.publicKey.toBase58());
+      expect(dareAccount.dareId.toNumber()).to.equal(900001);
+      expect(dareAccount.amount.toNumber()).to.equal(500_000_000);
+      expect(dareAccount.status).to.deep.equal({ created: {} });
+      expect(dareAccount.winnerCount).to.equal(1);
+      expect(dareAccount.payoutBps[0]).to.equal(10_000);
+      expect(dareAccount.acceptBy.toNumber()).to.equal(4102444800);
+    });
+
+    it("reports AccountNotMigrated for version 0 stats until they are migrated", async () => {
+      await airdrop(connection, legacyUser.publicKey);
+      const before = await connection.getAccountInfo(legacyStatsPDA);
+      expect(before.data.length).to.equal(73);
+
+      try {
+        await setupDare("created", 334, { from: legacyUser, amount: LAMPORTS_PER_SOL / 10 });
+        expect.fail("Should have thrown AccountNotMigrated");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AccountNotMigrated");
+      }
+
+      await program.methods.migrateUserStats()
+        .accounts({ payer: outsider.publicKey, userStats: legacyStatsPDA, systemProgram: SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+
+      const stats = await program.account.userStats.fetch(legacyStatsPDA);
+      expect(stats.version).to.equal(1);
+      expect(stats.daresCreated).to.equal(3);
+      expect(stats.totalSpent.toNumber()).to.equal(1_500_000_000);
+
+      await setupDare("created", 334, { from: legacyUser, amount: LAMPORTS_PER_SOL / 10 });
+      const migrated = await program.account.userStats.fetch(legacyStatsPDA);
+      expect(migrated.daresCreated).to.equal(4);
+    });
+
+    it("refuses accounts that aren't a dare", async () => {
+      try {
+        await program.methods.migrateDare()
+          .accounts({
+            payer: outsider.publicKey, dare: configPDA, proofAttempt: null, submission: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown UnknownAccountLayout");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnknownAccountLayout");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // reputation
+  // ---------------------------------------------------------------------------
+  describe("reputation", () => {
+    // Square root of the reward in 0.01 SOL units, plus completion points that reach 10 at 0.1 SOL,
+    // weighted from a quarter (challenger with no reputation) up to in full at 50 reputation
+    const completionPoints = (earned: number, challengerReputation = 0) => {
+      const units = Math.floor(earned / 10_000_000);
+      const points = Math.floor(Math.sqrt(units)) + Math.min(units, 10);
+      const shareBps = 2_500 + Math.floor((7_500 * Math.min(Math.max(challengerReputation, 0), 50)) / 50);
+      return Math.floor((points * shareBps) / 10_000);
+    };
+
+    async function accept(darePDA: PublicKey, who: Keypair) {
+      const [statsPDA] = getUserStatsPDA(program.programId, who.publicKey);
+      await program.methods.acceptDare()
+        .accounts({ daree: who.publicKey, dare: darePDA, dareeStats: statsPDA, systemProgram: SystemProgram.programId })
+        .signers([who])
+        .rpc();
+    }
+
+    it("credits completions by amount and halves repeat dealings with the same challenger", async () => {
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      await setupDare("approved", 315);
+      let stats = await program.account.userStats.fetch(dareeStatsPDA);
+      const firstEarned = stats.totalEarned.toNumber();
+      const first = completionPoints(firstEarned);
+      expect(stats.reputation.toNumber()).to.equal(first);
+
+      await setupDare("approved", 316);
+      stats = await program.account.userStats.fetch(dareeStatsPDA);
+      const second = completionPoints(stats.totalEarned.toNumber() - firstEarned) >> 1;
+      expect(stats.reputation.toNumber()).to.equal(first + second);
+      expect(stats.daresCompleted).to.equal(2);
+
+      const [dealingsPDA] = getDealingsPDA(program.programId, daree.publicKey, challenger.publicKey);
+      const dealings = await program.account.dealings.fetch(dealingsPDA);
+      expect(dealings.completions).to.equal(2);
+    });
+
+    it("scales completion points down for a near-empty dare", async () => {
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+
+      // 0.005 SOL is under one 0.01 SOL unit, so it earns neither amount nor completion points
+      await setupDare("approved", 335, { amount: 5_000_000 });
+      const stats = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(stats.daresCompleted).to.equal(1);
+      expect(stats.reputation.toNumber()).to.equal(0);
+    });
+
+    it("weighs the credit by the challenger's reputation when the dare was created", async () => {
+      const [dareeStatsPDA] = getUserStatsPDA(program.programId, daree.publicKey);
+      const [outsiderStatsPDA] = getUserStatsPDA(program.programId, outsider.publicKey);
+
+      // A fresh challenger passes on only a quarter of the credit
+      await setupDare("approved", 336);
+      const dareeStats = await program.account.userStats.fetch(dareeStatsPDA);
+      const dareeReputation = dareeStats.reputation.toNumber();
+      expect(dareeReputation).to.equal(completionPoints(dareeStats.totalEarned.toNumber()));
+
+      // The daree, now with some reputation, dares someone else and passes on more
+      const { darePDA } = await setupDare("approved", 337, { from: daree, to: outsider });
+      const dareAccount = await program.account.dare.fetch(darePDA);
+      expect(dareAccount.challengerReputation.toNumber()).to.equal(dareeReputation);
+      const outsiderStats = await program.account.userStats.fetch(outsiderStatsPDA);
+      const credited = completionPoints(outsiderStats.totalEarned.toNumber(), dareeReputation);
+      expect(credited).to.be.greaterThan(completionPoints(outsiderStats.totalEarned.toNumber()));
+      expect(outsiderStats.reputation.toNumber()).to.equal(credited);
+    });
+
+    it("penalizes a daree who gives up after accepting", async () => {
+      const { darePDA, vaultPDA, challengerStatsPDA, dareeStatsPDA } = await setupDare("active", 317);
+
+      await program.methods.refuseDare()
+        .accounts({
+          daree: daree.publicKey, dare: darePDA, vault: vaultPDA, challenger: challenger.publicKey,
+          challengerStats: challengerStatsPDA, dareeStats: dareeStatsPDA, systemProgram: SystemProgram.programId,
+        })
+        .signers([daree])
+        .rpc();
+
+      const stats = await program.account.userStats.fetch(dareeStatsPDA);
+      expect(stats.daresFailed).to.equal(1);
+      expect(stats.reputation.toNumber()).to.equal(-25);
+    });
+
+    it("keeps darees below a dare's minimum reputation out", async () => {
+      const gated = await setupDare("created", 318, { params: { minReputation: new BN(5) } });
+      try {
+        await accept(gated.darePDA, outsider);
+        expect.fail("Should have thrown ReputationTooLow");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ReputationTooLow");
+      }
+
+      // One completed dare clears the gate
+      await setupDare("approved", 319);
+      const next = await setupDare("created", 320, { params: { minReputation: new BN(5) } });
+      await accept(next.darePDA, daree);
+      const dareAccount = await program.account.dare.fetch(next.darePDA);
+      expect(dareAccount.status).to.deep.equal({ active: {} });
+      expect(dareAccount.minReputation.toNumber()).to.equal(5);
+    });
+  });
 });
@@ -21,6 +21,9 @@ import {
     buildTransaction,
     sha256Hash,
     deriveVaultPDA,
+    fetchDareState,
+    fetchTreasury,
+    fetchSubmissionAttempt,
 } from '../../../lib/anchor';
 import { hasXLinked, getXHandle, buildShareUrl, buildProofShareText, getDareUrl } from '../../../lib/twitter';
 
@@ -113,7 +116,7 @@ export default function DareDetail() {
             setTxHash(null);
 
             const connection = getConnection();
-            const instruction = buildFn();
+            const instruction = await buildFn();
             const tx = await buildTransaction(connection, walletPubkey, instruction);
             const serializedTx = tx.serialize({ requireAllSignatures: false });
 
@@ -159,16 +162,22 @@ export default function DareDetail() {
     };
 
     const handleApprove = () => {
-        if (!dare || !walletPubkey || !dare.daree?.walletAddress) return;
+        if (!dare || !walletPubkey || !dare.daree?.walletAddress || !dare.challenger?.walletAddress) return;
         const darePDA = new PublicKey(dare.darePDA);
         const [vaultPDA] = deriveVaultPDA(darePDA);
         sendTx('approve',
-            () => buildApproveDareInstruction({
-                challenger: walletPubkey,
-                darePDA,
-                vaultPDA,
-                daree: new PublicKey(dare.daree!.walletAddress!),
-            }),
+            async () => {
+                const connection = getConnection();
+                return buildApproveDareInstruction({
+                    authority: walletPubkey,
+                    challenger: new PublicKey(dare.challenger.walletAddress!),
+                    darePDA,
+                    vaultPDA,
+                    daree: new PublicKey(dare.daree!.walletAddress!),
+                    treasury: await fetchTreasury(connection),
+                    dareState: await fetchDareState(connection, darePDA),
+                });
+            },
             () => setOptimisticStatus('COMPLETED')
         );
     };
@@ -178,7 +187,16 @@ export default function DareDetail() {
         const darePDA = new PublicKey(dare.darePDA);
         // For Public Bounties, rejection resets to REJECTED (allows another submitter)
         sendTx('reject',
-            () => buildRejectDareInstruction({ challenger: walletPubkey, darePDA }),
+            async () => {
+                const connection = getConnection();
+                const { proofAttempts, takesSubmissions } = await fetchDareState(connection, darePDA);
+                if (takesSubmissions && dare.daree?.walletAddress) {
+                    const submitter = new PublicKey(dare.daree.walletAddress);
+                    const proofAttempt = await fetchSubmissionAttempt(connection, darePDA, submitter);
+                    return buildRejectDareInstruction({ authority: walletPubkey, darePDA, proofAttempt, submitter });
+                }
+                return buildRejectDareInstruction({ authority: walletPubkey, darePDA, proofAttempt: proofAttempts - 1 });
+            },
             () => setOptimisticStatus('REJECTED')
         );
     };
@@ -203,6 +221,7 @@ export default function DareDetail() {
                 darePDA,
                 vaultPDA,
                 challenger: new PublicKey(dare.challenger.walletAddress!),
+                accepted: dare.status === 'ACTIVE',
             }),
             () => setOptimisticStatus('CANCELLED')
         );
@@ -249,6 +268,7 @@ export default function DareDetail() {
                 submitter: walletPubkey,
                 darePDA,
                 proofHash: proofHashArray,
+                dareState: await fetchDareState(connection, darePDA),
             });
 
             const tx = await buildTransaction(connection, walletPubkey!, instruction);
@@ -22,6 +22,10 @@ const CATEGORIES = ['Fitness', 'Food', 'Social', 'Creative', 'Extreme', 'Gaming'
 // Zero public key (all zeros) — used as default target for open dares
 const PUBKEY_ZERO = new PublicKey(new Uint8Array(32));
 
+// Matches MAX_DESCRIPTION_LEN in the program, so the description can always be revealed on-chain
+const MAX_DESCRIPTION_BYTES = 800;
+const byteLength = (text: string) => new TextEncoder().encode(text).length;
+
 export default function CreateDare() {
     const { ready, authenticated, user: privyUser, linkTwitter } = usePrivy();
     const { ready: walletsReady, wallets } = useWallets();
@@ -89,7 +93,7 @@ export default function CreateDare() {
         if (!form.title.trim()) return 'Title is required';
         if (form.title.length > 100) return 'Title must be under 100 characters';
         if (!form.description.trim()) return 'Description is required';
-        if (form.description.length > 1000) return 'Description must be under 1000 characters';
+        if (byteLength(form.description.trim()) > MAX_DESCRIPTION_BYTES) return `Description must be at most ${MAX_DESCRIPTION_BYTES} bytes`;
         if (solAmount <= 0) return 'Amount must be greater than 0';
         if (solAmount > 100) return 'Max amount is 100 SOL';
         if (form.dareType === 'DIRECT_DARE' && !form.targetXHandle.trim()) {
@@ -403,11 +407,11 @@ export default function CreateDare() {
                             value={form.description}
                             onChange={(e) => update('description', e.target.value)}
                             placeholder="Describe the dare in detail. What needs to be done? How will proof be verified?"
-                            maxLength={1000}
+                            maxLength={MAX_DESCRIPTION_BYTES}
                             rows={4}
                             className="w-full rounded-lg border border-[var(--color-border)] bg-[var(--color-bg-card)] px-4 py-3 text-white placeholder-[var(--color-text-secondary)] outline-none transition-colors focus:border-[#FF6B35] resize-none"
                         />
-                        <p className="mt-1 text-right text-xs text-[var(--color-text-secondary)]">{form.description.length}/1000</p>
+                        <p className="mt-1 text-right text-xs text-[var(--color-text-secondary)]">{byteLength(form.description)}/{MAX_DESCRIPTION_BYTES}</p>
                     </div>
 
                     {/* Amount */}
@@ -1,10 +1,12 @@
 import {
+    AccountMeta,
     Connection,
     PublicKey,
     SystemProgram,
     Transaction,
     TransactionInstruction,
 } from '@solana/web3.js';
+import idl from './idl/contract.json';
 
 // ── Browser-safe helpers (NO Node.js Buffer!) ─────────────────────────────────
 
@@ -18,6 +20,20 @@ function bytesOf(...values: number[]): Uint8Array {
     return new Uint8Array(values);
 }
 
+/** Little-endian encode a u16 into 2 bytes */
+function encodeU16(value: number): Uint8Array {
+    const buf = new ArrayBuffer(2);
+    new DataView(buf).setUint16(0, value, true);
+    return new Uint8Array(buf);
+}
+
+/** Little-endian encode a u32 into 4 bytes */
+function encodeU32(value: number): Uint8Array {
+    const buf = new ArrayBuffer(4);
+    new DataView(buf).setUint32(0, value, true);
+    return new Uint8Array(buf);
+}
+
 /** Little-endian encode a u64 bigint into 8 bytes */
 function encodeU64(value: bigint): Uint8Array {
     const buf = new ArrayBuffer(8);
@@ -34,6 +50,11 @@ function encodeI64(value: bigint): Uint8Array {
     return new Uint8Array(buf);
 }
 
+/** Borsh Vec<u16>: u32 length prefix, then each element */
+function encodeVecU16(values: number[]): Uint8Array {
+    return concatBytes(encodeU32(values.length), ...values.map(encodeU16));
+}
+
 /** Concatenate multiple Uint8Arrays into one */
 function concatBytes(...arrays: Uint8Array[]): Uint8Array {
     const totalLength = arrays.reduce((sum, a) => sum + a.length, 0);
@@ -46,28 +67,48 @@ function concatBytes(...arrays: Uint8Array[]): Uint8Array {
     return result;
 }
 
+/** Byte-wise comparison, matching how the program orders Pubkeys */
+function compareBytes(a: Uint8Array, b: Uint8Array): number {
+    for (let i = 0; i < Math.min(a.length, b.length); i++) {
+        if (a[i] !== b[i]) return a[i] - b[i];
+    }
+    return a.length - b.length;
+}
+
 // ── Constants ─────────────────────────────────────────────────────────────────
-const PROGRAM_ID = new PublicKey(process.env.NEXT_PUBLIC_PROGRAM_ID || '8Vg3ximsFxoaEveSLQNe49i8tkSaeNubnxa54ypwXiD8');
+const PROGRAM_ID = new PublicKey(process.env.NEXT_PUBLIC_PROGRAM_ID || idl.address);
 const DARE_SEED = strToBytes('dare');
 const VAULT_SEED = strToBytes('vault');
 const USER_STATS_SEED = strToBytes('user_stats');
+const CONFIG_SEED = strToBytes('config');
+const SUBMISSION_SEED = strToBytes('submission');
+const PROOF_ATTEMPT_SEED = strToBytes('proof_attempt');
+const DEALINGS_SEED = strToBytes('dealings');
+const EVENT_AUTHORITY_SEED = strToBytes('__event_authority');
 
 // ── IDL Discriminators ────────────────────────────────────────────────────────
-// Pre-computed from the IDL (sha256("global:<instruction_name>")[0..8])
+// Read from the IDL (sha256("global:<instruction_name>")[0..8])
+function instructionDiscriminator(name: string): Uint8Array {
+    const instruction = idl.instructions.find((ix) => ix.name === name);
+    if (!instruction) throw new Error(`Instruction ${name} is not in the IDL`);
+    return new Uint8Array(instruction.discriminator);
+}
+
 const DISCRIMINATORS = {
-    createDare: bytesOf(165, 248, 7, 27, 99, 187, 25, 198),
-    acceptDare: bytesOf(238, 123, 72, 103, 159, 234, 210, 83),
-    submitProof: bytesOf(54, 241, 46, 84, 4, 212, 46, 94),
-    approveDare: bytesOf(75, 217, 114, 212, 39, 128, 254, 190),
-    rejectDare: bytesOf(24, 236, 243, 244, 209, 25, 109, 91),
-    cancelDare: bytesOf(170, 254, 168, 239, 96, 236, 53, 126),
-    refuseDare: bytesOf(29, 81, 37, 218, 38, 163, 183, 9),
-    expireDare: bytesOf(250, 215, 157, 210, 10, 18, 124, 61),
+    createDare: instructionDiscriminator('create_dare'),
+    acceptDare: instructionDiscriminator('accept_dare'),
+    submitProof: instructionDiscriminator('submit_proof'),
+    approveDare: instructionDiscriminator('approve_dare'),
+    rejectDare: instructionDiscriminator('reject_dare'),
+    cancelDare: instructionDiscriminator('cancel_dare'),
+    refuseDare: instructionDiscriminator('refuse_dare'),
+    expireDare: instructionDiscriminator('expire_dare'),
 };
 
 // ── Enum Values ───────────────────────────────────────────────────────────────
 const DARE_TYPE_VALUES = { DirectDare: 0, PublicBounty: 1 } as const;
 const WINNER_SELECTION_VALUES = { ChallengerSelect: 0, CommunityVote: 1 } as const;
+const MISS_POLICY_VALUES = { EndDare: 0, ForfeitSlice: 1 } as const;
 
 // ── PDA Derivation ────────────────────────────────────────────────────────────
 export function deriveDarePDA(challenger: PublicKey, dareId: bigint): [PublicKey, number] {
@@ -91,6 +132,99 @@ export function deriveUserStatsPDA(user: PublicKey): [PublicKey, number] {
     );
 }
 
+export function deriveConfigPDA(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync([CONFIG_SEED], PROGRAM_ID);
+}
+
+export function deriveSubmissionPDA(darePDA: PublicKey, submitter: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [SUBMISSION_SEED, darePDA.toBuffer(), submitter.toBuffer()],
+        PROGRAM_ID
+    );
+}
+
+export function deriveProofAttemptPDA(darePDA: PublicKey, index: number): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [PROOF_ATTEMPT_SEED, darePDA.toBuffer(), encodeU32(index)],
+        PROGRAM_ID
+    );
+}
+
+/** The pair's Dealings account; seeded by the lower pubkey first, so either order works */
+export function deriveDealingsPDA(a: PublicKey, b: PublicKey): [PublicKey, number] {
+    const [lower, higher] = compareBytes(a.toBytes(), b.toBytes()) <= 0 ? [a, b] : [b, a];
+    return PublicKey.findProgramAddressSync(
+        [DEALINGS_SEED, lower.toBuffer(), higher.toBuffer()],
+        PROGRAM_ID
+    );
+}
+
+function deriveEventAuthorityPDA(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync([EVENT_AUTHORITY_SEED], PROGRAM_ID);
+}
+
+// ── Account Metas ─────────────────────────────────────────────────────────────
+
+/** An optional account left out: Anchor reads the program ID in its slot as None */
+function omitted(): AccountMeta {
+    return { pubkey: PROGRAM_ID, isSigner: false, isWritable: false };
+}
+
+/** Accounts every instruction that emits events takes last (#[event_cpi]) */
+function eventCpiAccounts(): AccountMeta[] {
+    return [
+        { pubkey: deriveEventAuthorityPDA()[0], isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+    ];
+}
+
+// ── On-chain Reads ────────────────────────────────────────────────────────────
+// Byte offsets into the account layouts in the IDL, counting the 8-byte discriminator
+const DARE_TYPE_OFFSET = 155;
+const DARE_WINNER_SELECTION_OFFSET = 156;
+const DARE_PROOF_ATTEMPTS_OFFSET = 408;
+const CONFIG_TREASURY_OFFSET = 40;
+const SUBMISSION_ATTEMPT_OFFSET = 114;
+
+export interface DareState {
+    /** Proofs submitted so far; the next ProofAttempt takes this index */
+    proofAttempts: number;
+    /** PublicBounty dares settled by the challenger take one Submission per submitter */
+    takesSubmissions: boolean;
+}
+
+async function fetchAccountData(connection: Connection, address: PublicKey): Promise<DataView> {
+    const info = await connection.getAccountInfo(address);
+    if (!info) throw new Error(`Account ${address.toBase58()} not found`);
+    return new DataView(info.data.buffer, info.data.byteOffset, info.data.byteLength);
+}
+
+export async function fetchDareState(connection: Connection, darePDA: PublicKey): Promise<DareState> {
+    const data = await fetchAccountData(connection, darePDA);
+    return {
+        proofAttempts: data.getUint32(DARE_PROOF_ATTEMPTS_OFFSET, true),
+        takesSubmissions: data.getUint8(DARE_TYPE_OFFSET) === DARE_TYPE_VALUES.PublicBounty
+            && data.getUint8(DARE_WINNER_SELECTION_OFFSET) === WINNER_SELECTION_VALUES.ChallengerSelect,
+    };
+}
+
+/** Protocol treasury from the config, which settlements pay the fee to */
+export async function fetchTreasury(connection: Connection): Promise<PublicKey> {
+    const data = await fetchAccountData(connection, deriveConfigPDA()[0]);
+    return new PublicKey(new Uint8Array(data.buffer, data.byteOffset + CONFIG_TREASURY_OFFSET, 32));
+}
+
+/** ProofAttempt index of a bounty submission, which a rejection stamps */
+export async function fetchSubmissionAttempt(
+    connection: Connection,
+    darePDA: PublicKey,
+    submitter: PublicKey
+): Promise<number> {
+    const [submissionPDA] = deriveSubmissionPDA(darePDA, submitter);
+    const data = await fetchAccountData(connection, submissionPDA);
+    return data.getUint32(SUBMISSION_ATTEMPT_OFFSET, true);
+}
+
 // ── SHA-256 hash (browser Web Crypto API) ─────────────────────────────────────
 export async function sha256Hash(data: string): Promise<Uint8Array> {
     const encoded = new TextEncoder().encode(data);
@@ -112,6 +246,34 @@ export function getConnection(): Connection {
 }
 
 // ── Transaction Builders ──────────────────────────────────────────────────────
+// Account order follows the IDL. The web app only escrows SOL, so the token accounts are omitted.
+
+/** create_dare settings beyond the target (CreateDareParams); anything left out keeps its default */
+export interface DareSettings {
+    payoutSharesBps: number[];
+    dareeStake: bigint;
+    streakPeriod: bigint;
+    streakPeriods: number;
+    missPolicy: 'EndDare' | 'ForfeitSlice';
+    judge: PublicKey;
+    judgeFeeBps: number;
+    disputeWindow: bigint;
+    timeToComplete: bigint;
+    minReputation: bigint;
+}
+
+const DEFAULT_DARE_SETTINGS: DareSettings = {
+    payoutSharesBps: [],
+    dareeStake: BigInt(0),
+    streakPeriod: BigInt(0),
+    streakPeriods: 0,
+    missPolicy: 'EndDare',
+    judge: new PublicKey(new Uint8Array(32)),
+    judgeFeeBps: 0,
+    disputeWindow: BigInt(0),
+    timeToComplete: BigInt(0),
+    minReputation: BigInt(0),
+};
 
 /**
  * Build the createDare instruction.
@@ -126,6 +288,7 @@ export function buildCreateDareInstruction(params: {
     dareType: 'DirectDare' | 'PublicBounty';
     winnerSelection: 'ChallengerSelect' | 'CommunityVote';
     targetDaree: PublicKey; // new PublicKey(new Uint8Array(32)) for open dares
+    settings?: Partial<DareSettings>;
 }): {
     instruction: TransactionInstruction;
     darePDA: PublicKey;
@@ -133,12 +296,16 @@ export function buildCreateDareInstruction(params: {
     challengerStatsPDA: PublicKey;
 } {
     const { challenger, dareId, descriptionHash, amount, deadline, dareType, winnerSelection, targetDaree } = params;
+    const settings = { ...DEFAULT_DARE_SETTINGS, ...params.settings };
 
     const [darePDA] = deriveDarePDA(challenger, dareId);
     const [vaultPDA] = deriveVaultPDA(darePDA);
     const [challengerStatsPDA] = deriveUserStatsPDA(challenger);
+    const [configPDA] = deriveConfigPDA();
 
-    // Serialize args: dare_id(u64) + description_hash([u8;32]) + amount(u64) + deadline(i64) + dare_type(u8) + winner_selection(u8) + target_daree(32 bytes)
+    // Serialize args: dare_id(u64) + description_hash([u8;32]) + amount(u64) + deadline(i64) + dare_type(u8) + winner_selection(u8)
+    // + params: target_daree(32) + payout_shares_bps(vec<u16>) + daree_stake(u64) + streak_period(i64) + streak_periods(u8)
+    // + miss_policy(u8) + judge(32) + judge_fee_bps(u16) + dispute_window(i64) + time_to_complete(i64) + min_reputation(i64)
     const data = concatBytes(
         DISCRIMINATORS.createDare,
         encodeU64(dareId),
@@ -148,15 +315,33 @@ export function buildCreateDareInstruction(params: {
         bytesOf(DARE_TYPE_VALUES[dareType]),
         bytesOf(WINNER_SELECTION_VALUES[winnerSelection]),
         targetDaree.toBytes(),
+        encodeVecU16(settings.payoutSharesBps),
+        encodeU64(settings.dareeStake),
+        encodeI64(settings.streakPeriod),
+        bytesOf(settings.streakPeriods),
+        bytesOf(MISS_POLICY_VALUES[settings.missPolicy]),
+        settings.judge.toBytes(),
+        encodeU16(settings.judgeFeeBps),
+        encodeI64(settings.disputeWindow),
+        encodeI64(settings.timeToComplete),
+        encodeI64(settings.minReputation),
     );
 
     const instruction = new TransactionInstruction({
         keys: [
             { pubkey: challenger, isSigner: true, isWritable: true },
             { pubkey: darePDA, isSigner: false, isWritable: true },
+            { pubkey: configPDA, isSigner: false, isWritable: false },
             { pubkey: vaultPDA, isSigner: false, isWritable: true },
             { pubkey: challengerStatsPDA, isSigner: false, isWritable: true },
+            omitted(), // mint
+            omitted(), // allowed_mint
+            omitted(), // challenger_token_account
+            omitted(), // vault_token_account
+            omitted(), // token_program
+            omitted(), // associated_token_program
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            ...eventCpiAccounts(),
         ],
         programId: PROGRAM_ID,
         data: data as Buffer,
@@ -174,6 +359,7 @@ export function buildAcceptDareInstruction(params: {
 }): TransactionInstruction {
     const { daree, darePDA } = params;
     const [dareeStatsPDA] = deriveUserStatsPDA(daree);
+    const [vaultPDA] = deriveVaultPDA(darePDA);
 
     const data = DISCRIMINATORS.acceptDare;
 
@@ -181,8 +367,15 @@ export function buildAcceptDareInstruction(params: {
         keys: [
             { pubkey: daree, isSigner: true, isWritable: true },
             { pubkey: darePDA, isSigner: false, isWritable: true },
+            { pubkey: deriveConfigPDA()[0], isSigner: false, isWritable: false },
             { pubkey: dareeStatsPDA, isSigner: false, isWritable: true },
+            { pubkey: vaultPDA, isSigner: false, isWritable: true },
+            omitted(), // mint
+            omitted(), // daree_token_account
+            omitted(), // vault_token_account
+            omitted(), // token_program
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            ...eventCpiAccounts(),
         ],
         programId: PROGRAM_ID,
         data: data as Buffer,
@@ -191,14 +384,18 @@ export function buildAcceptDareInstruction(params: {
 
 /**
  * Build the submitProof instruction.
+ * `dareState` comes from fetchDareState: the proof lands in the next ProofAttempt.
  */
 export function buildSubmitProofInstruction(params: {
     submitter: PublicKey;
     darePDA: PublicKey;
     proofHash: Uint8Array; // 32 bytes
+    dareState: DareState;
 }): TransactionInstruction {
-    const { submitter, darePDA, proofHash } = params;
+    const { submitter, darePDA, proofHash, dareState } = params;
     const [submitterStatsPDA] = deriveUserStatsPDA(submitter);
+    const [vaultPDA] = deriveVaultPDA(darePDA);
+    const [proofAttemptPDA] = deriveProofAttemptPDA(darePDA, dareState.proofAttempts);
 
     const data = concatBytes(DISCRIMINATORS.submitProof, proofHash);
 
@@ -206,8 +403,19 @@ export function buildSubmitProofInstruction(params: {
         keys: [
             { pubkey: submitter, isSigner: true, isWritable: true },
             { pubkey: darePDA, isSigner: false, isWritable: true },
+            { pubkey: deriveConfigPDA()[0], isSigner: false, isWritable: false },
             { pubkey: submitterStatsPDA, isSigner: false, isWritable: true },
+            dareState.takesSubmissions
+                ? { pubkey: deriveSubmissionPDA(darePDA, submitter)[0], isSigner: false, isWritable: true }
+                : omitted(),
+            { pubkey: proofAttemptPDA, isSigner: false, isWritable: true },
+            { pubkey: vaultPDA, isSigner: false, isWritable: true },
+            omitted(), // mint
+            omitted(), // submitter_token_account
+            omitted(), // vault_token_account
+            omitted(), // token_program
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            ...eventCpiAccounts(),
         ],
         programId: PROGRAM_ID,
         data: data as Buffer,
@@ -216,26 +424,46 @@ export function buildSubmitProofInstruction(params: {
 
 /**
  * Build the approveDare instruction.
+ * `authority` is the challenger, or the judge when the dare has one.
  */
 export function buildApproveDareInstruction(params: {
+    authority: PublicKey;
     challenger: PublicKey;
     darePDA: PublicKey;
     vaultPDA: PublicKey;
     daree: PublicKey;
+    treasury: PublicKey; // fetchTreasury
+    dareState: DareState;
 }): TransactionInstruction {
-    const { challenger, darePDA, vaultPDA, daree } = params;
+    const { authority, challenger, darePDA, vaultPDA, daree, treasury, dareState } = params;
+    const [authorityStatsPDA] = deriveUserStatsPDA(authority);
     const [dareeStatsPDA] = deriveUserStatsPDA(daree);
+    const [dealingsPDA] = deriveDealingsPDA(challenger, daree);
 
     const data = DISCRIMINATORS.approveDare;
 
     return new TransactionInstruction({
         keys: [
-            { pubkey: challenger, isSigner: true, isWritable: true },
+            { pubkey: authority, isSigner: true, isWritable: true },
             { pubkey: darePDA, isSigner: false, isWritable: true },
+            { pubkey: authorityStatsPDA, isSigner: false, isWritable: true },
             { pubkey: vaultPDA, isSigner: false, isWritable: true },
             { pubkey: daree, isSigner: false, isWritable: true },
             { pubkey: dareeStatsPDA, isSigner: false, isWritable: true },
+            dareState.takesSubmissions
+                ? { pubkey: deriveSubmissionPDA(darePDA, daree)[0], isSigner: false, isWritable: true }
+                : omitted(),
+            { pubkey: dealingsPDA, isSigner: false, isWritable: true },
+            { pubkey: deriveConfigPDA()[0], isSigner: false, isWritable: false },
+            { pubkey: treasury, isSigner: false, isWritable: true },
+            omitted(), // mint
+            omitted(), // vault_token_account
+            omitted(), // daree_token_account
+            omitted(), // authority_token_account
+            omitted(), // treasury_token_account
+            omitted(), // token_program
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            ...eventCpiAccounts(),
         ],
         programId: PROGRAM_ID,
         data: data as Buffer,
@@ -244,19 +472,33 @@ export function buildApproveDareInstruction(params: {
 
 /**
  * Build the rejectDare instruction.
+ * `proofAttempt` is the rejected proof's ProofAttempt index: the latest one (proofAttempts - 1),
+ * or the submission's own for bounties (fetchSubmissionAttempt), which also pass `submitter`.
  */
 export function buildRejectDareInstruction(params: {
-    challenger: PublicKey;
+    authority: PublicKey;
     darePDA: PublicKey;
+    proofAttempt: number;
+    submitter?: PublicKey;
+    reasonHash?: Uint8Array; // 32 bytes, all zeros for no reason
 }): TransactionInstruction {
-    const { challenger, darePDA } = params;
+    const { authority, darePDA, proofAttempt, submitter } = params;
+    const [authorityStatsPDA] = deriveUserStatsPDA(authority);
+    const [proofAttemptPDA] = deriveProofAttemptPDA(darePDA, proofAttempt);
 
-    const data = DISCRIMINATORS.rejectDare;
+    const data = concatBytes(DISCRIMINATORS.rejectDare, params.reasonHash ?? new Uint8Array(32));
 
     return new TransactionInstruction({
         keys: [
-            { pubkey: challenger, isSigner: true, isWritable: false },
+            { pubkey: authority, isSigner: true, isWritable: true },
             { pubkey: darePDA, isSigner: false, isWritable: true },
+            { pubkey: authorityStatsPDA, isSigner: false, isWritable: true },
+            submitter
+                ? { pubkey: deriveSubmissionPDA(darePDA, submitter)[0], isSigner: false, isWritable: true }
+                : omitted(),
+            { pubkey: proofAttemptPDA, isSigner: false, isWritable: true },
+            { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            ...eventCpiAccounts(),
         ],
         programId: PROGRAM_ID,
         data: data as Buffer,
@@ -282,7 +524,12 @@ export function buildCancelDareInstruction(params: {
             { pubkey: darePDA, isSigner: false, isWritable: true },
             { pubkey: vaultPDA, isSigner: false, isWritable: true },
             { pubkey: challengerStatsPDA, isSigner: false, isWritable: true },
+            omitted(), // mint
+            omitted(), // vault_token_account
+            omitted(), // challenger_token_account
+            omitted(), // token_program
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            ...eventCpiAccounts(),
         ],
         programId: PROGRAM_ID,
         data: data as Buffer,
@@ -291,14 +538,16 @@ export function buildCancelDareInstruction(params: {
 
 /**
  * Build the refuseDare instruction.
+ * A daree refusing after accepting also passes their stats, which record the failure.
  */
 export function buildRefuseDareInstruction(params: {
     daree: PublicKey;
     darePDA: PublicKey;
     vaultPDA: PublicKey;
     challenger: PublicKey;
+    accepted: boolean;
 }): TransactionInstruction {
-    const { daree, darePDA, vaultPDA, challenger } = params;
+    const { daree, darePDA, vaultPDA, challenger, accepted } = params;
     const [challengerStatsPDA] = deriveUserStatsPDA(challenger);
 
     const data = DISCRIMINATORS.refuseDare;
@@ -310,7 +559,15 @@ export function buildRefuseDareInstruction(params: {
             { pubkey: vaultPDA, isSigner: false, isWritable: true },
             { pubkey: challenger, isSigner: false, isWritable: true },
             { pubkey: challengerStatsPDA, isSigner: false, isWritable: true },
+            accepted
+                ? { pubkey: deriveUserStatsPDA(daree)[0], isSigner: false, isWritable: true }
+                : omitted(),
+            omitted(), // mint
+            omitted(), // vault_token_account
+            omitted(), // challenger_token_account
+            omitted(), // token_program
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            ...eventCpiAccounts(),
         ],
         programId: PROGRAM_ID,
         data: data as Buffer,